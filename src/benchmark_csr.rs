@@ -0,0 +1,42 @@
+extern crate test;
+
+use test::Bencher;
+use super::generators;
+use super::graph::Bfs;
+use super::graph_io::EdgeList;
+use super::undirected_graph::UndirectedGraph;
+
+// 10k nodes with an average degree of 400: two million edges.
+const N_NODES: usize = 10_000;
+const DEGREE: usize = 400;
+
+// Unit weights: Dijkstra does the same work whatever the weights.
+fn random_graph() -> EdgeList {
+    generators::erdos_renyi(N_NODES, DEGREE as f64 / (N_NODES - 1) as f64, 1)
+}
+
+// Both BFS benchmarks run the same generic traversal, so they only differ
+// by the memory layout of the adjacency.
+#[bench]
+fn bench_bfs_adjacency_lists(b: &mut Bencher) {
+    let g: UndirectedGraph<()> = random_graph().to_undirected();
+    b.iter(|| Bfs::new(&g, 0).count())
+}
+
+#[bench]
+fn bench_bfs_csr(b: &mut Bencher) {
+    let g = random_graph().to_weighted().freeze();
+    b.iter(|| Bfs::new(&g, 0).count())
+}
+
+#[bench]
+fn bench_dijkstra_adjacency_lists(b: &mut Bencher) {
+    let g = random_graph().to_weighted();
+    b.iter(|| g.get_dijkstra(0))
+}
+
+#[bench]
+fn bench_dijkstra_csr(b: &mut Bencher) {
+    let g = random_graph().to_weighted().freeze();
+    b.iter(|| g.get_dijkstra(0))
+}
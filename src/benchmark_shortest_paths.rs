@@ -0,0 +1,88 @@
+extern crate test;
+
+use test::Bencher;
+use super::generators;
+use super::weighted_graph::WeightedGraph;
+
+const N_NODES: usize = 100;
+
+// Random graph with an average degree of `degree` and weights in 1..=100.
+fn seeded_graph(n: usize, degree: usize) -> WeightedGraph {
+    let mut list = generators::erdos_renyi(n, degree as f64 / (n - 1) as f64, 1);
+    generators::random_weights(&mut list, 1..101, 1);
+    list.to_weighted()
+}
+
+#[bench]
+fn bench_floyd_sparse(b: &mut Bencher) {
+    let g = seeded_graph(N_NODES, 4);
+    b.iter(|| g.get_floyd())
+}
+
+#[bench]
+fn bench_johnson_sparse(b: &mut Bencher) {
+    let g = seeded_graph(N_NODES, 4);
+    b.iter(|| g.get_johnson())
+}
+
+#[bench]
+fn bench_floyd_medium(b: &mut Bencher) {
+    let g = seeded_graph(N_NODES, 16);
+    b.iter(|| g.get_floyd())
+}
+
+#[bench]
+fn bench_johnson_medium(b: &mut Bencher) {
+    let g = seeded_graph(N_NODES, 16);
+    b.iter(|| g.get_johnson())
+}
+
+#[bench]
+fn bench_floyd_dense(b: &mut Bencher) {
+    let g = seeded_graph(N_NODES, 64);
+    b.iter(|| g.get_floyd())
+}
+
+#[bench]
+fn bench_johnson_dense(b: &mut Bencher) {
+    let g = seeded_graph(N_NODES, 64);
+    b.iter(|| g.get_johnson())
+}
+
+const N_NODES_LARGE: usize = 400;
+
+#[bench]
+fn bench_floyd_large(b: &mut Bencher) {
+    let g = seeded_graph(N_NODES_LARGE, 16);
+    b.iter(|| g.get_floyd())
+}
+
+#[bench]
+fn bench_floyd_parallel_1_thread_large(b: &mut Bencher) {
+    let g = seeded_graph(N_NODES_LARGE, 16);
+    b.iter(|| g.get_floyd_parallel(1))
+}
+
+#[bench]
+fn bench_floyd_parallel_4_threads_large(b: &mut Bencher) {
+    let g = seeded_graph(N_NODES_LARGE, 16);
+    b.iter(|| g.get_floyd_parallel(4))
+}
+
+// 16 stripes of FLOYD_BLOCK rows, enough to keep 4 threads busy; at 400
+// nodes there are only 7. Each run is about a billion relaxations, and
+// the bencher takes at least 50 of them: twice the nodes would make each
+// benchmark take minutes.
+const N_NODES_HUGE: usize = 1024;
+
+#[bench]
+fn bench_floyd_parallel_1_thread_huge(b: &mut Bencher) {
+    let g = seeded_graph(N_NODES_HUGE, 16);
+    b.iter(|| g.get_floyd_parallel(1))
+}
+
+#[bench]
+fn bench_floyd_parallel_4_threads_huge(b: &mut Bencher) {
+    let g = seeded_graph(N_NODES_HUGE, 16);
+    b.iter(|| g.get_floyd_parallel(4))
+}
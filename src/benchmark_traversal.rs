@@ -0,0 +1,52 @@
+extern crate test;
+
+use test::Bencher;
+use super::generators;
+use super::undirected_graph::UndirectedGraph;
+
+// Large enough that a quadratic traversal would take minutes.
+const N_NODES: usize = 200_000;
+
+fn with_data(mut g: UndirectedGraph<usize>) -> UndirectedGraph<usize> {
+    for u in 0..g.size() {
+        g.set_data(u, u);
+    }
+    g
+}
+
+fn sparse_graph() -> UndirectedGraph<usize> {
+    with_data(generators::erdos_renyi(N_NODES, 4.0 / (N_NODES - 1) as f64, 1).to_undirected())
+}
+
+// Worst case for rescanning adjacency lists: every node is on the DFS path.
+fn path_graph() -> UndirectedGraph<usize> {
+    let mut g = UndirectedGraph::new(N_NODES);
+    for u in 1..N_NODES {
+        g.add_edge(u - 1, u);
+    }
+    with_data(g)
+}
+
+#[bench]
+fn bench_bfs_sparse(b: &mut Bencher) {
+    let mut g = sparse_graph();
+    b.iter(|| g.bfs(0).count())
+}
+
+#[bench]
+fn bench_dfs_sparse(b: &mut Bencher) {
+    let mut g = sparse_graph();
+    b.iter(|| g.dfs(0).count())
+}
+
+#[bench]
+fn bench_bfs_path(b: &mut Bencher) {
+    let mut g = path_graph();
+    b.iter(|| g.bfs(0).count())
+}
+
+#[bench]
+fn bench_dfs_path(b: &mut Bencher) {
+    let mut g = path_graph();
+    b.iter(|| g.dfs(0).count())
+}
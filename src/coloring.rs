@@ -0,0 +1,314 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use graph::GraphBase;
+use heap::BinaryHeap;
+
+/// Order in which `greedy_coloring` colors the nodes. Self-loops are
+/// ignored by every coloring routine.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColoringOrder {
+    /// Node 0 first, then node 1, and so on.
+    Natural,
+    /// Repeatedly set aside a node of smallest remaining degree, then color
+    /// in reverse. Uses at most one more color than the graph's degeneracy.
+    SmallestLast,
+    /// Next is the node with the most distinct colors among its neighbors,
+    /// ties going to the largest degree (Brélaz).
+    DSatur
+}
+
+/// Gives every node the smallest color, numbered from 0, that none of its
+/// already colored neighbors has.
+pub fn greedy_coloring<G: GraphBase>(graph: &G, order: ColoringOrder) -> Vec<usize> {
+    match order {
+        ColoringOrder::Natural => color_in_order(graph, 0..graph.node_count()),
+        ColoringOrder::SmallestLast => {
+            let mut order = smallest_last_order(graph);
+            order.reverse();
+            color_in_order(graph, order.into_iter())
+        }
+        ColoringOrder::DSatur => dsatur(graph)
+    }
+}
+
+const NONE: usize = usize::MAX;
+
+fn smallest_color<G: GraphBase>(graph: &G, colors: &[usize], u: usize) -> usize {
+    let taken: HashSet<usize> = graph.adjacent(u).filter(|&v| v != u).map(|v| colors[v]).collect();
+    (0..).find(|c| !taken.contains(c)).unwrap()
+}
+
+fn color_in_order<G: GraphBase, I: Iterator<Item = usize>>(graph: &G, order: I) -> Vec<usize> {
+    let mut colors = vec![NONE; graph.node_count()];
+    for u in order {
+        colors[u] = smallest_color(graph, &colors, u);
+    }
+    colors
+}
+
+// Nodes in the order they are removed, always removing one of smallest
+// degree in what is left, in O(V + E) with buckets of nodes by degree.
+fn smallest_last_order<G: GraphBase>(graph: &G) -> Vec<usize> {
+    let n = graph.node_count();
+    let mut degree: Vec<usize> = (0..n).map(|u| graph.adjacent(u).filter(|&v| v != u).count()).collect();
+    // Parallel edges can make a degree reach n or more.
+    let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); degree.iter().max().map_or(0, |&d| d + 1)];
+    for u in 0..n {
+        buckets[degree[u]].push(u);
+    }
+    let mut removed = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    let mut d = 0;
+    while order.len() < n {
+        // Buckets hold stale entries for nodes whose degree went down since.
+        let u = match buckets[d].pop() {
+            Some(u) if removed[u] || degree[u] != d => continue,
+            Some(u) => u,
+            None => {
+                d += 1;
+                continue;
+            }
+        };
+        removed[u] = true;
+        order.push(u);
+        for v in graph.adjacent(u) {
+            if !removed[v] && v != u {
+                degree[v] -= 1;
+                buckets[degree[v]].push(v);
+                d = d.min(degree[v]);
+            }
+        }
+    }
+    order
+}
+
+fn dsatur<G: GraphBase>(graph: &G) -> Vec<usize> {
+    let n = graph.node_count();
+    let mut colors = vec![NONE; n];
+    let mut neighbor_colors: Vec<HashSet<usize>> = vec![HashSet::new(); n];
+    // Entries go stale when a node gets colored or its saturation grows.
+    let mut heap = BinaryHeap::new();
+    for u in 0..n {
+        heap.insert((Reverse(0), Reverse(graph.degree(u)), u));
+    }
+    while !heap.is_empty() {
+        let (Reverse(saturation), _, u) = heap.remove_min();
+        if colors[u] != NONE || saturation != neighbor_colors[u].len() {
+            continue;
+        }
+        let c = smallest_color(graph, &colors, u);
+        colors[u] = c;
+        for v in graph.adjacent(u) {
+            if colors[v] == NONE && neighbor_colors[v].insert(c) {
+                heap.insert((Reverse(neighbor_colors[v].len()), Reverse(graph.degree(v)), v));
+            }
+        }
+    }
+    colors
+}
+
+/// Coloring with at most `k` colors found by backtracking, or None if there
+/// is none. Exponential in the worst case: meant for small graphs.
+pub fn k_coloring<G: GraphBase>(graph: &G, k: usize) -> Option<Vec<usize>> {
+    let n = graph.node_count();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|&u| Reverse(graph.degree(u)));
+    let mut colors = vec![NONE; n];
+    if extend_coloring(graph, &order, k, 0, &mut colors) {
+        Some(colors)
+    } else {
+        None
+    }
+}
+
+// Colors the nodes of `order` from the `i`th on. Colors are tried up to one
+// more than the largest used so far, since unused colors are interchangeable.
+fn extend_coloring<G: GraphBase>(graph: &G, order: &[usize], k: usize, n_used: usize, colors: &mut Vec<usize>) -> bool {
+    let u = match order.first() {
+        Some(&u) => u,
+        None => return true
+    };
+    for c in 0..k.min(n_used + 1) {
+        if graph.adjacent(u).any(|v| v != u && colors[v] == c) {
+            continue;
+        }
+        colors[u] = c;
+        if extend_coloring(graph, &order[1..], k, n_used.max(c + 1), colors) {
+            return true;
+        }
+    }
+    colors[u] = NONE;
+    false
+}
+
+/// Whether `colors` gives every node a color, different from the colors
+/// of its neighbors.
+pub fn is_proper_coloring<G: GraphBase>(graph: &G, colors: &[usize]) -> bool {
+    colors.len() == graph.node_count() &&
+        (0..colors.len()).all(|u| graph.adjacent(u).all(|v| v == u || colors[v] != colors[u]))
+}
+
+/// Independent set that no other node can be added to, picking nodes of
+/// smallest degree first. Nodes are in increasing order.
+pub fn maximal_independent_set<G: GraphBase>(graph: &G) -> Vec<usize> {
+    let n = graph.node_count();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|&u| graph.degree(u));
+    let mut blocked = vec![false; n];
+    let mut set = Vec::new();
+    for u in order {
+        if blocked[u] {
+            continue;
+        }
+        set.push(u);
+        blocked[u] = true;
+        for v in graph.adjacent(u) {
+            blocked[v] = true;
+        }
+    }
+    set.sort();
+    set
+}
+
+/// Whether no two nodes of `set` are adjacent.
+pub fn is_independent_set<G: GraphBase>(graph: &G, set: &[usize]) -> bool {
+    let mut member = vec![false; graph.node_count()];
+    for &u in set.iter() {
+        if !graph.contains_node(u) {
+            return false;
+        }
+        member[u] = true;
+    }
+    set.iter().all(|&u| graph.adjacent(u).all(|v| v == u || !member[v]))
+}
+
+/// Whether `set` is independent and every other node has a neighbor in it.
+/// Removed nodes are not part of the graph, so they need no neighbor.
+pub fn is_maximal_independent_set<G: GraphBase>(graph: &G, set: &[usize]) -> bool {
+    if !is_independent_set(graph, set) {
+        return false;
+    }
+    let mut covered = vec![false; graph.node_count()];
+    for &u in set.iter() {
+        covered[u] = true;
+        for v in graph.adjacent(u) {
+            covered[v] = true;
+        }
+    }
+    (0..covered.len()).all(|u| covered[u] || !graph.contains_node(u))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{greedy_coloring, is_independent_set, is_maximal_independent_set, is_proper_coloring,
+                k_coloring, maximal_independent_set, ColoringOrder};
+    use test_util::{graph, Lcg};
+    use undirected_graph::UndirectedGraph;
+
+    const ORDERS: [ColoringOrder; 3] = [ColoringOrder::Natural, ColoringOrder::SmallestLast, ColoringOrder::DSatur];
+
+    fn n_colors(colors: &[usize]) -> usize {
+        colors.iter().map(|&c| c + 1).max().unwrap_or(0)
+    }
+
+    fn random_graph(rng: &mut Lcg, n: usize, m: usize) -> UndirectedGraph<()> {
+        let edges: Vec<(usize, usize)> = (0..m).map(|_| (rng.next(n), rng.next(n))).collect();
+        graph(n, &edges)
+    }
+
+    #[test]
+    fn validators() {
+        let s = graph(3, &[(0, 1), (1, 2), (2, 2)]);
+        assert!(is_proper_coloring(&s, &[0, 1, 0]));
+        assert!(!is_proper_coloring(&s, &[0, 0, 1]));
+        assert!(!is_proper_coloring(&s, &[0, 1]));
+        assert!(is_independent_set(&s, &[0, 2]));
+        assert!(!is_independent_set(&s, &[0, 1]));
+        assert!(!is_independent_set(&s, &[3]));
+        assert!(is_maximal_independent_set(&s, &[0, 2]));
+        assert!(is_maximal_independent_set(&s, &[1]));
+        assert!(!is_maximal_independent_set(&s, &[0]));
+    }
+
+    #[test]
+    fn crown_graph() {
+        // Crown graph: u_i - v_j for i != j. Bipartite, yet coloring in the
+        // natural order u_0 v_0 u_1 v_1 ... needs one color per pair.
+        let n = 5;
+        let mut edges = Vec::new();
+        for i in 0..n {
+            for j in 0..n {
+                if i != j {
+                    edges.push((2 * i, 2 * j + 1));
+                }
+            }
+        }
+        let s = graph(2 * n, &edges);
+        let natural = greedy_coloring(&s, ColoringOrder::Natural);
+        assert!(is_proper_coloring(&s, &natural));
+        assert_eq!(n_colors(&natural), n);
+        for &order in [ColoringOrder::SmallestLast, ColoringOrder::DSatur].iter() {
+            let colors = greedy_coloring(&s, order);
+            assert!(is_proper_coloring(&s, &colors));
+            assert_eq!(n_colors(&colors), 2, "{:?}", order);
+        }
+    }
+
+    #[test]
+    fn exact_coloring() {
+        // Odd wheel: a 5-cycle plus a hub needs 4 colors.
+        let s = graph(6, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (5, 0), (5, 1), (5, 2), (5, 3), (5, 4)]);
+        assert_eq!(k_coloring(&s, 3), None);
+        let colors = k_coloring(&s, 4).unwrap();
+        assert!(is_proper_coloring(&s, &colors));
+        assert_eq!(k_coloring(&graph(0, &[]), 0), Some(vec![]));
+        assert_eq!(k_coloring(&graph(1, &[(0, 0)]), 1), Some(vec![0]));
+        assert_eq!(k_coloring(&graph(1, &[]), 0), None);
+    }
+
+    #[test]
+    fn random_colorings() {
+        let mut rng = Lcg(3);
+        for _ in 0..40 {
+            let n = 1 + rng.next(9);
+            let m = rng.next(3 * n);
+            let s = random_graph(&mut rng, n, m);
+            let chromatic = (0..=n).find(|&k| k_coloring(&s, k).is_some()).unwrap();
+            for &order in ORDERS.iter() {
+                let colors = greedy_coloring(&s, order);
+                assert!(is_proper_coloring(&s, &colors));
+                assert!(n_colors(&colors) >= chromatic);
+            }
+            let colors = k_coloring(&s, chromatic).unwrap();
+            assert!(is_proper_coloring(&s, &colors));
+            assert!(n_colors(&colors) <= chromatic);
+        }
+    }
+
+    #[test]
+    fn independent_sets() {
+        let star = graph(5, &[(0, 1), (0, 2), (0, 3), (0, 4)]);
+        assert_eq!(maximal_independent_set(&star), vec![1, 2, 3, 4]);
+        let mut rng = Lcg(5);
+        for _ in 0..40 {
+            let n = 1 + rng.next(20);
+            let m = rng.next(3 * n);
+            let s = random_graph(&mut rng, n, m);
+            assert!(is_maximal_independent_set(&s, &maximal_independent_set(&s)));
+        }
+    }
+
+    #[test]
+    fn large_smallest_last() {
+        // A long path is 1-degenerate: smallest-last needs only 2 colors.
+        let n = 100_000;
+        let edges: Vec<(usize, usize)> = (1..n).map(|u| (u - 1, u)).collect();
+        let s = graph(n, &edges);
+        for &order in ORDERS.iter() {
+            let colors = greedy_coloring(&s, order);
+            assert!(is_proper_coloring(&s, &colors));
+            assert_eq!(n_colors(&colors), 2);
+        }
+    }
+}
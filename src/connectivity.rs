@@ -0,0 +1,254 @@
+use graph::GraphBase;
+
+/// Component id of every node. Ids are numbered from 0 in the order of
+/// the smallest node of each component.
+pub fn connected_components<G: GraphBase>(graph: &G) -> Vec<usize> {
+    const UNSEEN: usize = usize::MAX;
+    let mut component = vec![UNSEEN; graph.node_count()];
+    let mut stack = Vec::new();
+    let mut n_components = 0;
+    for root in 0..graph.node_count() {
+        if component[root] != UNSEEN {
+            continue;
+        }
+        component[root] = n_components;
+        stack.push(root);
+        while let Some(u) = stack.pop() {
+            for v in graph.adjacent(u) {
+                if component[v] == UNSEEN {
+                    component[v] = n_components;
+                    stack.push(v);
+                }
+            }
+        }
+        n_components += 1;
+    }
+    component
+}
+
+/// Finds a cycle in an undirected graph, as the list of its nodes in order.
+/// A self-loop is a cycle of one node and two parallel edges a cycle of two.
+pub fn find_cycle<G: GraphBase>(graph: &G) -> Option<Vec<usize>> {
+    const UNSEEN: usize = usize::MAX;
+    let n = graph.node_count();
+    // Position of each node on the current DFS path
+    let mut depth = vec![UNSEEN; n];
+    let mut visited = vec![false; n];
+    // (node, parent, whether the edge to the parent was skipped, remaining neighbors)
+    let mut stack = Vec::new();
+    for root in 0..n {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        depth[root] = 0;
+        stack.push((root, None, false, graph.adjacent(root)));
+        while let Some(&mut (u, parent, ref mut skipped_parent, ref mut adjacent)) = stack.last_mut() {
+            match adjacent.next() {
+                Some(v) if Some(v) == parent && !*skipped_parent => *skipped_parent = true,
+                Some(v) if visited[v] => {
+                    // In an undirected DFS, this edge leads back to an ancestor.
+                    return Some(stack[depth[v]..].iter().map(|&(w, _, _, _)| w).collect());
+                }
+                Some(v) => {
+                    visited[v] = true;
+                    depth[v] = stack.len();
+                    stack.push((v, Some(u), false, graph.adjacent(v)));
+                }
+                None => {
+                    depth[u] = UNSEEN;
+                    stack.pop();
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Result of Tarjan's bridges and articulation points algorithm.
+pub struct Biconnectivity {
+    /// Edges whose removal disconnects their component, as `(u, v)` with `u < v`.
+    pub bridges: Vec<(usize, usize)>,
+    /// Nodes whose removal disconnects their component, in increasing order.
+    pub articulation_points: Vec<usize>,
+    /// Edges of each biconnected component. Self-loops belong to none.
+    pub components: Vec<Vec<(usize, usize)>>
+}
+
+/// Tarjan's algorithm, run without recursion so that deep graphs do not
+/// overflow the stack. Parallel edges are never bridges.
+pub fn biconnectivity<G: GraphBase>(graph: &G) -> Biconnectivity {
+    const UNSEEN: usize = usize::MAX;
+    let n = graph.node_count();
+    let mut result = Biconnectivity {
+        bridges: Vec::new(),
+        articulation_points: Vec::new(),
+        components: Vec::new()
+    };
+    let mut discovered = vec![UNSEEN; n];
+    let mut low = vec![0; n];
+    let mut is_articulation = vec![false; n];
+    let mut time = 0;
+    let mut edges: Vec<(usize, usize)> = Vec::new();
+    // (node, parent, whether the edge to the parent was skipped, remaining neighbors)
+    let mut stack = Vec::new();
+
+    for root in 0..n {
+        if discovered[root] != UNSEEN {
+            continue;
+        }
+        discovered[root] = time;
+        low[root] = time;
+        time += 1;
+        let mut root_children = 0;
+        stack.push((root, None, false, graph.adjacent(root)));
+
+        while let Some(&mut (u, parent, ref mut skipped_parent, ref mut adjacent)) = stack.last_mut() {
+            if let Some(v) = adjacent.next() {
+                if Some(v) == parent && !*skipped_parent {
+                    // Only the tree edge itself is skipped, not its parallel edges.
+                    *skipped_parent = true;
+                } else if discovered[v] == UNSEEN {
+                    discovered[v] = time;
+                    low[v] = time;
+                    time += 1;
+                    edges.push((u, v));
+                    stack.push((v, Some(u), false, graph.adjacent(v)));
+                } else if discovered[v] < discovered[u] {
+                    low[u] = low[u].min(discovered[v]);
+                    edges.push((u, v));
+                }
+                continue;
+            }
+            stack.pop();
+            let p = match parent {
+                Some(p) => p,
+                None => continue
+            };
+            low[p] = low[p].min(low[u]);
+            if low[u] > discovered[p] {
+                result.bridges.push((p.min(u), p.max(u)));
+            }
+            if low[u] >= discovered[p] {
+                if p == root {
+                    root_children += 1;
+                } else {
+                    is_articulation[p] = true;
+                }
+                let mut component = Vec::new();
+                while let Some(e) = edges.pop() {
+                    component.push(e);
+                    if e == (p, u) {
+                        break;
+                    }
+                }
+                result.components.push(component);
+            }
+        }
+        if root_children >= 2 {
+            is_articulation[root] = true;
+        }
+    }
+    result.articulation_points = (0..n).filter(|&u| is_articulation[u]).collect();
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::{biconnectivity, connected_components, find_cycle};
+    use test_util::graph;
+    use undirected_graph::UndirectedGraph;
+
+    fn sorted(mut component: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+        component = component.into_iter().map(|(u, v)| (u.min(v), u.max(v))).collect();
+        component.sort();
+        component
+    }
+
+    #[test]
+    fn components() {
+        let s = graph(7, &[(0, 1), (1, 2), (3, 4), (6, 6)]);
+        assert_eq!(connected_components(&s), vec![0, 0, 0, 1, 1, 2, 3]);
+        assert!(connected_components(&graph(0, &[])).is_empty());
+    }
+
+    #[test]
+    fn bridges_and_articulation_points() {
+        // Two triangles joined by the bridge 2-3, plus a pendant node 6.
+        let s = graph(7, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3), (5, 6)]);
+        let b = biconnectivity(&s);
+        assert_eq!(b.bridges, vec![(5, 6), (2, 3)]);
+        assert_eq!(b.articulation_points, vec![2, 3, 5]);
+        let mut components: Vec<_> = b.components.into_iter().map(sorted).collect();
+        components.sort();
+        assert_eq!(components, vec![
+            vec![(0, 1), (0, 2), (1, 2)],
+            vec![(2, 3)],
+            vec![(3, 4), (3, 5), (4, 5)],
+            vec![(5, 6)]
+        ]);
+    }
+
+    #[test]
+    fn root_articulation_point() {
+        let s = graph(3, &[(0, 1), (0, 2)]);
+        let b = biconnectivity(&s);
+        assert_eq!(b.articulation_points, vec![0]);
+        assert_eq!(b.bridges, vec![(0, 1), (0, 2)]);
+
+        let s = graph(3, &[(0, 1), (1, 2)]);
+        assert_eq!(biconnectivity(&s).articulation_points, vec![1]);
+    }
+
+    #[test]
+    fn parallel_edges_and_self_loops() {
+        let s = graph(3, &[(0, 1), (0, 1), (1, 2), (2, 2)]);
+        let b = biconnectivity(&s);
+        assert_eq!(b.bridges, vec![(1, 2)]);
+        assert_eq!(b.articulation_points, vec![1]);
+        assert_eq!(b.components.len(), 2);
+    }
+
+    #[test]
+    fn cycle_has_no_bridge() {
+        let s = graph(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        let b = biconnectivity(&s);
+        assert!(b.bridges.is_empty());
+        assert!(b.articulation_points.is_empty());
+        assert_eq!(b.components.len(), 1);
+        assert_eq!(b.components[0].len(), 4);
+    }
+
+    fn assert_cycle(s: &UndirectedGraph<()>, cycle: &[usize]) {
+        for i in 0..cycle.len() {
+            let next = cycle[(i + 1) % cycle.len()];
+            assert!(s.from(cycle[i]).contains(&next), "{} - {}", cycle[i], next);
+        }
+    }
+
+    #[test]
+    fn cycles() {
+        assert_eq!(find_cycle(&graph(4, &[(0, 1), (1, 2), (1, 3)])), None);
+
+        let s = graph(6, &[(0, 1), (4, 5), (1, 2), (2, 3), (3, 1)]);
+        let cycle = find_cycle(&s).unwrap();
+        assert_eq!(cycle, vec![1, 2, 3]);
+        assert_cycle(&s, &cycle);
+
+        assert_eq!(find_cycle(&graph(3, &[(0, 1), (2, 2)])), Some(vec![2]));
+        assert_eq!(find_cycle(&graph(3, &[(0, 1), (1, 2), (2, 1)])), Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn deep_path_does_not_overflow() {
+        let n = 200_000;
+        let edges: Vec<(usize, usize)> = (1..n).map(|u| (u - 1, u)).collect();
+        let s = graph(n, &edges);
+        let b = biconnectivity(&s);
+        assert_eq!(b.bridges.len(), n - 1);
+        assert_eq!(b.articulation_points.len(), n - 2);
+        assert_eq!(b.components.len(), n - 1);
+        assert!(connected_components(&s).iter().all(|&c| c == 0));
+        assert_eq!(find_cycle(&s), None);
+    }
+}
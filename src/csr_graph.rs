@@ -0,0 +1,149 @@
+use std::iter::{Cloned, Zip};
+use std::slice::Iter;
+
+use graph::{Bfs, Dfs, GraphBase, WeightedEdges};
+use shortest_paths;
+
+/// Immutable graph in compressed sparse row form: the adjacency of every
+/// node lives in the same few contiguous buffers.
+/// Built with `WeightedGraph::freeze` or `UndirectedGraph::freeze`.
+pub struct CsrGraph {
+    // Neighbors of u are targets[offsets[u]..offsets[u + 1]]
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<i32>
+}
+
+impl CsrGraph {
+    /// Freezes any graph, keeping the order of neighbors.
+    pub fn build<G: WeightedEdges>(graph: &G) -> CsrGraph {
+        let n_nodes = graph.node_count();
+        let mut g = CsrGraph {
+            offsets: Vec::with_capacity(n_nodes + 1),
+            targets: Vec::new(),
+            weights: Vec::new()
+        };
+        g.offsets.push(0);
+        for u in 0..n_nodes {
+            for (v, weight) in graph.weighted_adjacent(u) {
+                g.targets.push(v);
+                g.weights.push(weight);
+            }
+            g.offsets.push(g.targets.len());
+        }
+        g
+    }
+
+    pub fn size(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn from(&self, u: usize) -> &[usize] {
+        &self.targets[self.offsets[u]..self.offsets[u + 1]]
+    }
+
+    /// Iterates over the `(neighbor, weight)` pairs of `u`, one per edge.
+    pub fn neighbors(&self, u: usize) -> impl Iterator<Item = (usize, i32)> + '_ {
+        self.weighted_adjacent(u)
+    }
+
+    /// Depth-first traversal yielding node indices, in the same order as
+    /// `UndirectedGraph::dfs`.
+    pub fn dfs(&self, start: usize) -> Dfs<'_, CsrGraph> {
+        Dfs::new(self, start)
+    }
+
+    /// Breadth-first traversal yielding node indices.
+    pub fn bfs(&self, start: usize) -> Bfs<'_, CsrGraph> {
+        Bfs::new(self, start)
+    }
+
+    /// Single source shortest path lengths from `source` (Dijkstra).
+    /// All weights must be non-negative. Unreachable nodes get i32::MAX.
+    pub fn get_dijkstra(&self, source: usize) -> Vec<i32> {
+        shortest_paths::dijkstra(self, source)
+    }
+}
+
+impl GraphBase for CsrGraph {
+    type Adjacent<'a> = Cloned<Iter<'a, usize>>;
+
+    fn node_count(&self) -> usize {
+        self.size()
+    }
+
+    fn adjacent(&self, u: usize) -> Self::Adjacent<'_> {
+        self.from(u).iter().cloned()
+    }
+
+    fn degree(&self, u: usize) -> usize {
+        self.offsets[u + 1] - self.offsets[u]
+    }
+}
+
+impl WeightedEdges for CsrGraph {
+    type WeightedAdjacent<'a> = Zip<Cloned<Iter<'a, usize>>, Cloned<Iter<'a, i32>>>;
+
+    fn weighted_adjacent(&self, u: usize) -> Self::WeightedAdjacent<'_> {
+        let range = self.offsets[u]..self.offsets[u + 1];
+        self.targets[range.clone()].iter().cloned().zip(self.weights[range].iter().cloned())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use graph_io::EdgeList;
+    use test_util::sample_graph;
+    use undirected_graph::UndirectedGraph;
+    use weighted_graph::WeightedGraph;
+
+    // The sample graph, with each node's index as its data.
+    fn sample_undirected() -> UndirectedGraph<usize> {
+        let mut s: UndirectedGraph<usize> = EdgeList::from_weighted(&sample_graph()).to_undirected();
+        for u in 0..s.size() {
+            s.set_data(u, u);
+        }
+        s
+    }
+
+    #[test]
+    fn freeze_undirected() {
+        let s = sample_undirected();
+        let csr = s.freeze();
+        assert_eq!(csr.size(), 10);
+        for u in 0..10 {
+            assert_eq!(csr.from(u), &s.from(u)[..]);
+        }
+        assert_eq!(csr.neighbors(1).collect::<Vec<_>>(), vec![(0, 1), (2, 1), (6, 1), (8, 1)]);
+    }
+
+    #[test]
+    fn freeze_weighted() {
+        let mut g = WeightedGraph::new(4);
+        g.add_edge(0, 1, 4);
+        g.add_edge(1, 2, 1);
+        g.add_edge(0, 2, 7);
+        let csr = g.freeze();
+        for u in 0..4 {
+            assert_eq!(csr.neighbors(u).collect::<Vec<_>>(), g.neighbors(u).collect::<Vec<_>>());
+        }
+        assert_eq!(csr.get_dijkstra(0), g.get_dijkstra(0));
+        assert_eq!(csr.get_dijkstra(0), vec![0, 4, 5, i32::MAX]);
+    }
+
+    #[test]
+    fn empty() {
+        let csr = WeightedGraph::new(0).freeze();
+        assert_eq!(csr.size(), 0);
+    }
+
+    #[test]
+    fn traversals_match_undirected_graph() {
+        let mut s = sample_undirected();
+        let csr = s.freeze();
+        for start in 0..10 {
+            assert_eq!(csr.dfs(start).collect::<Vec<_>>(), s.dfs(start).collect::<Vec<_>>());
+            assert_eq!(csr.bfs(start).collect::<Vec<_>>(), s.bfs(start).collect::<Vec<_>>());
+        }
+    }
+}
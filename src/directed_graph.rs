@@ -0,0 +1,373 @@
+use std::fmt;
+use std::iter::{Cloned, Map};
+use std::slice::Iter;
+
+use graph::{self, DfsEvent, GraphBase, WeightedEdges};
+
+/// Directed graph: `add_edge(u, v)` only links u to v.
+pub struct DirectedGraph {
+    out: Vec<Vec<usize>>
+}
+
+/// A cycle found while sorting, as the list of its nodes: each node has an
+/// edge to the next one, and the last node has an edge to the first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cycle {
+    pub nodes: Vec<usize>
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cycle through nodes {:?}", self.nodes)
+    }
+}
+
+impl ::std::error::Error for Cycle {}
+
+impl DirectedGraph {
+    pub fn new(size: usize) -> DirectedGraph {
+        DirectedGraph {
+            out: vec![Vec::new(); size]
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.out.len()
+    }
+
+    /// Adds an isolated node and returns its index.
+    pub fn add_node(&mut self) -> usize {
+        self.out.push(Vec::new());
+        self.out.len() - 1
+    }
+
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        assert!(u < self.size());
+        assert!(v < self.size());
+        self.out[u].push(v);
+    }
+
+    /// Successors of `u`.
+    pub fn from(&self, u: usize) -> &Vec<usize> {
+        &self.out[u]
+    }
+
+    /// Depth-first search from `start`, calling `visitor` on every
+    /// discovery, finish and classified edge.
+    pub fn dfs_visit<F: FnMut(DfsEvent)>(&self, start: usize, visitor: F) {
+        graph::dfs_visit(self, start, visitor)
+    }
+
+    /// Orders the nodes so that every edge goes from an earlier node to a
+    /// later one, or returns one of the cycles that make it impossible.
+    pub fn topological_sort(&self) -> Result<Vec<usize>, Cycle> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State { Unseen, OnStack, Done }
+
+        let n = self.size();
+        let mut state = vec![State::Unseen; n];
+        let mut postorder = Vec::with_capacity(n);
+        // (node, index of the next successor to look at)
+        let mut stack: Vec<(usize, usize)> = Vec::new();
+        for root in 0..n {
+            if state[root] != State::Unseen {
+                continue;
+            }
+            state[root] = State::OnStack;
+            stack.push((root, 0));
+            while let Some(&mut (u, ref mut next)) = stack.last_mut() {
+                if *next == self.out[u].len() {
+                    state[u] = State::Done;
+                    postorder.push(u);
+                    stack.pop();
+                    continue;
+                }
+                let v = self.out[u][*next];
+                *next += 1;
+                match state[v] {
+                    State::Unseen => {
+                        state[v] = State::OnStack;
+                        stack.push((v, 0));
+                    }
+                    State::OnStack => {
+                        let start = stack.iter().position(|&(w, _)| w == v).unwrap();
+                        return Err(Cycle { nodes: stack[start..].iter().map(|&(w, _)| w).collect() });
+                    }
+                    State::Done => {}
+                }
+            }
+        }
+        postorder.reverse();
+        Ok(postorder)
+    }
+
+    /// Strongly connected component id of every node (Tarjan).
+    /// Ids follow a topological order of the components: an edge between
+    /// two components always goes to the larger id.
+    pub fn strongly_connected_components(&self) -> Vec<usize> {
+        const UNSEEN: usize = usize::MAX;
+        let n = self.size();
+        let mut index = vec![UNSEEN; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut component = vec![UNSEEN; n];
+        let mut members = Vec::new();
+        let mut time = 0;
+        let mut n_components = 0;
+        let mut stack: Vec<(usize, usize)> = Vec::new();
+        for root in 0..n {
+            if index[root] != UNSEEN {
+                continue;
+            }
+            stack.push((root, 0));
+            index[root] = time;
+            low[root] = time;
+            time += 1;
+            members.push(root);
+            on_stack[root] = true;
+            while let Some(&mut (u, ref mut next)) = stack.last_mut() {
+                if let Some(&v) = self.out[u].get(*next) {
+                    *next += 1;
+                    if index[v] == UNSEEN {
+                        index[v] = time;
+                        low[v] = time;
+                        time += 1;
+                        members.push(v);
+                        on_stack[v] = true;
+                        stack.push((v, 0));
+                    } else if on_stack[v] {
+                        low[u] = low[u].min(index[v]);
+                    }
+                    continue;
+                }
+                stack.pop();
+                if let Some(&(p, _)) = stack.last() {
+                    low[p] = low[p].min(low[u]);
+                }
+                if low[u] == index[u] {
+                    while let Some(w) = members.pop() {
+                        on_stack[w] = false;
+                        component[w] = n_components;
+                        if w == u {
+                            break;
+                        }
+                    }
+                    n_components += 1;
+                }
+            }
+        }
+        // Tarjan finds the components in reverse topological order.
+        component.iter().map(|&c| n_components - 1 - c).collect()
+    }
+
+    /// Condensation of the graph: one node per strongly connected component
+    /// (numbered as in `strongly_connected_components`, returned alongside),
+    /// and one edge between two components when any of their nodes are linked.
+    /// The result has no cycles.
+    pub fn condensation(&self) -> (DirectedGraph, Vec<usize>) {
+        let component = self.strongly_connected_components();
+        let n_components = component.iter().map(|&c| c + 1).max().unwrap_or(0);
+        let mut members = vec![Vec::new(); n_components];
+        for u in 0..self.size() {
+            members[component[u]].push(u);
+        }
+        let mut dag = DirectedGraph::new(n_components);
+        // last_seen[c] == cu once the edge cu -> c has been added
+        let mut last_seen = vec![usize::MAX; n_components];
+        for (cu, nodes) in members.iter().enumerate() {
+            for &u in nodes.iter() {
+                for &v in self.out[u].iter() {
+                    let cv = component[v];
+                    if cv != cu && last_seen[cv] != cu {
+                        last_seen[cv] = cu;
+                        dag.add_edge(cu, cv);
+                    }
+                }
+            }
+        }
+        (dag, component)
+    }
+}
+
+impl GraphBase for DirectedGraph {
+    type Adjacent<'a> = Cloned<Iter<'a, usize>>;
+
+    fn node_count(&self) -> usize {
+        self.size()
+    }
+
+    fn adjacent(&self, u: usize) -> Self::Adjacent<'_> {
+        self.out[u].iter().cloned()
+    }
+
+    fn degree(&self, u: usize) -> usize {
+        self.out[u].len()
+    }
+}
+
+/// Every edge has weight 1.
+impl WeightedEdges for DirectedGraph {
+    type WeightedAdjacent<'a> = Map<Iter<'a, usize>, fn(&usize) -> (usize, i32)>;
+
+    fn weighted_adjacent(&self, u: usize) -> Self::WeightedAdjacent<'_> {
+        self.out[u].iter().map(|&v| (v, 1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Cycle, DirectedGraph};
+    use graph::{Bfs, DfsEvent};
+    use shortest_paths;
+    use test_util::digraph;
+
+    fn assert_topological(g: &DirectedGraph, order: &[usize]) {
+        let mut position = vec![0; g.size()];
+        for (i, &u) in order.iter().enumerate() {
+            position[u] = i;
+        }
+        assert_eq!(order.len(), g.size());
+        for u in 0..g.size() {
+            for &v in g.from(u) {
+                assert!(position[u] < position[v], "edge {} -> {}", u, v);
+            }
+        }
+    }
+
+    #[test]
+    fn basics() {
+        let mut g = digraph(2, &[(0, 1)]);
+        assert_eq!(g.add_node(), 2);
+        g.add_edge(2, 0);
+        assert_eq!(g.from(0), &[1]);
+        assert!(g.from(1).is_empty());
+        assert_eq!(Bfs::new(&g, 2).collect::<Vec<_>>(), vec![2, 0, 1]);
+        assert_eq!(Bfs::new(&g, 1).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(shortest_paths::dijkstra(&g, 0), vec![0, 1, i32::MAX]);
+    }
+
+    #[test]
+    fn topological_sort() {
+        // Build steps: 5 and 4 before 0, 5 before 2, 2 before 3, 3 before 1, 4 before 1
+        let g = digraph(6, &[(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)]);
+        let order = g.topological_sort().unwrap();
+        assert_topological(&g, &order);
+        assert_eq!(order, vec![5, 4, 2, 3, 1, 0]);
+        assert_eq!(digraph(0, &[]).topological_sort(), Ok(vec![]));
+    }
+
+    #[test]
+    fn topological_sort_cycle() {
+        let g = digraph(5, &[(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)]);
+        let err = g.topological_sort().unwrap_err();
+        assert_eq!(err, Cycle { nodes: vec![1, 2, 3] });
+        assert_eq!(err.to_string(), "cycle through nodes [1, 2, 3]");
+
+        assert_eq!(digraph(1, &[(0, 0)]).topological_sort(), Err(Cycle { nodes: vec![0] }));
+    }
+
+    #[test]
+    fn dfs_visit() {
+        let g = digraph(5, &[(0, 1), (1, 2), (0, 2), (2, 0), (3, 1), (0, 4)]);
+        let mut events = Vec::new();
+        g.dfs_visit(0, |e| events.push(e));
+        assert_eq!(events, vec![
+            DfsEvent::Discover { node: 0, time: 0 },
+            DfsEvent::TreeEdge(0, 1),
+            DfsEvent::Discover { node: 1, time: 1 },
+            DfsEvent::TreeEdge(1, 2),
+            DfsEvent::Discover { node: 2, time: 2 },
+            DfsEvent::BackEdge(2, 0),
+            DfsEvent::Finish { node: 2, time: 3 },
+            DfsEvent::Finish { node: 1, time: 4 },
+            DfsEvent::ForwardEdge(0, 2),
+            DfsEvent::TreeEdge(0, 4),
+            DfsEvent::Discover { node: 4, time: 5 },
+            DfsEvent::Finish { node: 4, time: 6 },
+            DfsEvent::Finish { node: 0, time: 7 }
+        ]);
+
+        let mut cross = Vec::new();
+        digraph(3, &[(0, 1), (0, 2), (2, 1)]).dfs_visit(0, |e| if let DfsEvent::CrossEdge(u, v) = e {
+            cross.push((u, v));
+        });
+        assert_eq!(cross, vec![(2, 1)]);
+    }
+
+    #[test]
+    fn finish_order_sorts_a_dag() {
+        let g = digraph(6, &[(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)]);
+        let mut order = Vec::new();
+        let mut seen = vec![false; g.size()];
+        for root in 0..g.size() {
+            if seen[root] {
+                continue;
+            }
+            g.dfs_visit(root, |e| match e {
+                DfsEvent::Discover { node, .. } => seen[node] = true,
+                DfsEvent::Finish { node, .. } if !order.contains(&node) => order.push(node),
+                _ => {}
+            });
+        }
+        order.reverse();
+        assert_topological(&g, &order);
+    }
+
+    #[test]
+    fn strongly_connected_components() {
+        let g = digraph(8, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3), (6, 5), (6, 7), (7, 6)]);
+        let scc = g.strongly_connected_components();
+        assert_eq!(scc[0], scc[1]);
+        assert_eq!(scc[1], scc[2]);
+        assert_eq!(scc[3], scc[4]);
+        assert_eq!(scc[4], scc[5]);
+        assert_eq!(scc[6], scc[7]);
+        assert!(scc[0] < scc[3]);
+        assert!(scc[6] < scc[3]);
+        assert_eq!(scc.iter().max(), Some(&2));
+    }
+
+    #[test]
+    fn condensation() {
+        let g = digraph(6, &[(0, 1), (1, 0), (1, 2), (2, 3), (3, 2), (0, 3), (4, 4)]);
+        let (dag, component) = g.condensation();
+        assert_eq!(dag.size(), 4);
+        assert_eq!(component[0], component[1]);
+        assert_eq!(component[2], component[3]);
+        assert_eq!(dag.from(component[0]), &[component[2]]);
+        assert!(dag.from(component[4]).is_empty());
+        let order = dag.topological_sort().unwrap();
+        assert_topological(&dag, &order);
+    }
+
+    #[test]
+    fn dense_condensation() {
+        // Two cycles of 500 nodes, with every node of the first linked to
+        // every node of the second: 250k edges between two components.
+        let n = 500;
+        let mut g = DirectedGraph::new(2 * n);
+        for u in 0..n {
+            g.add_edge(u, (u + 1) % n);
+            g.add_edge(n + u, n + (u + 1) % n);
+            for v in n..2 * n {
+                g.add_edge(u, v);
+            }
+        }
+        let (dag, component) = g.condensation();
+        assert_eq!(dag.size(), 2);
+        assert_eq!(dag.from(component[0]), &[component[n]]);
+        assert!(dag.from(component[n]).is_empty());
+    }
+
+    #[test]
+    fn deep_graph_does_not_overflow() {
+        let n = 200_000;
+        let edges: Vec<(usize, usize)> = (1..n).map(|u| (u - 1, u)).collect();
+        let mut g = digraph(n, &edges);
+        assert_eq!(g.topological_sort().unwrap(), (0..n).collect::<Vec<_>>());
+        assert_eq!(g.strongly_connected_components(), (0..n).collect::<Vec<_>>());
+        g.add_edge(n - 1, 0);
+        assert!(g.strongly_connected_components().iter().all(|&c| c == 0));
+        assert_eq!(g.topological_sort().unwrap_err().nodes.len(), n);
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt::Write;
+
+use graph_io::EdgeList;
+
+/// Turns a path given as a sequence of nodes into the list of its edges,
+/// to highlight it with `to_dot_highlighted`.
+pub fn path_edges(path: &[usize]) -> Vec<(usize, usize)> {
+    path.windows(2).map(|w| (w[0], w[1])).collect()
+}
+
+// Renders an undirected graph in Graphviz DOT. Nodes without a label are
+// labelled with their index, and nodes marked in `removed` are left out.
+// Edges in `highlight` (in either direction) and their endpoints are drawn in red.
+pub fn render(list: &EdgeList, labels: &[Option<String>], removed: &[bool], weighted: bool, highlight: &[(usize, usize)]) -> String {
+    let is_highlighted = |u: usize, v: usize| {
+        highlight.iter().any(|&(a, b)| (a, b) == (u, v) || (a, b) == (v, u))
+    };
+    let mut dot = String::from("graph {\n");
+    for u in (0..list.n_nodes).filter(|&u| removed.get(u) != Some(&true)) {
+        let label = match labels.get(u) {
+            Some(Some(label)) => escape(label),
+            _ => u.to_string()
+        };
+        write!(dot, "    {} [label=\"{}\"", u, label).unwrap();
+        if highlight.iter().any(|&(a, b)| a == u || b == u) {
+            dot.push_str(", color=red");
+        }
+        dot.push_str("];\n");
+    }
+    for &(u, v, weight) in list.edges.iter() {
+        let mut attributes = Vec::new();
+        if weighted {
+            attributes.push(format!("label=\"{}\"", weight));
+        }
+        if is_highlighted(u, v) {
+            attributes.push("color=red, penwidth=2".to_string());
+        }
+        if attributes.is_empty() {
+            writeln!(dot, "    {} -- {};", u, v).unwrap();
+        } else {
+            writeln!(dot, "    {} -- {} [{}];", u, v, attributes.join(", ")).unwrap();
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod test {
+    use super::path_edges;
+
+    #[test]
+    fn path_to_edges() {
+        assert_eq!(path_edges(&[3, 1, 4]), vec![(3, 1), (1, 4)]);
+        assert!(path_edges(&[3]).is_empty());
+        assert!(path_edges(&[]).is_empty());
+    }
+}
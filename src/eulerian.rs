@@ -0,0 +1,206 @@
+use std::fmt;
+
+use graph::GraphBase;
+
+/// Why a graph has no Eulerian circuit or path.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotEulerian {
+    /// Nodes of odd degree, in increasing order: a circuit allows none of
+    /// them and a path at most two.
+    OddDegree(Vec<usize>),
+    /// Two nodes with edges but no path between them.
+    Disconnected(usize, usize)
+}
+
+impl fmt::Display for NotEulerian {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NotEulerian::OddDegree(ref nodes) => write!(f, "nodes {:?} have an odd degree", nodes),
+            NotEulerian::Disconnected(u, v) => write!(f, "no path between nodes {} and {}", u, v)
+        }
+    }
+}
+
+impl ::std::error::Error for NotEulerian {}
+
+/// Closed walk using every edge exactly once (Hierholzer), as the list of
+/// its nodes: one more than the number of edges, the last being the first.
+/// The graph is undirected: it lists every edge from both of its ends and
+/// a self-loop twice at its node. Parallel edges and self-loops are allowed.
+/// Empty when there are no edges.
+pub fn eulerian_circuit<G: GraphBase>(graph: &G) -> Result<Vec<usize>, NotEulerian> {
+    let odd = odd_degree_nodes(graph);
+    if !odd.is_empty() {
+        return Err(NotEulerian::OddDegree(odd));
+    }
+    let edges = undirected_edges(graph);
+    match edges.first() {
+        Some(&(start, _)) => hierholzer(graph.node_count(), &edges, start),
+        None => Ok(Vec::new())
+    }
+}
+
+/// Walk using every edge exactly once (Hierholzer), as the list of its
+/// nodes. It starts at the smallest node of odd degree if there is one,
+/// and is a circuit otherwise. Empty when there are no edges.
+pub fn eulerian_path<G: GraphBase>(graph: &G) -> Result<Vec<usize>, NotEulerian> {
+    let odd = odd_degree_nodes(graph);
+    if odd.len() > 2 {
+        return Err(NotEulerian::OddDegree(odd));
+    }
+    let edges = undirected_edges(graph);
+    match odd.first().cloned().or_else(|| edges.first().map(|&(u, _)| u)) {
+        Some(start) => hierholzer(graph.node_count(), &edges, start),
+        None => Ok(Vec::new())
+    }
+}
+
+fn odd_degree_nodes<G: GraphBase>(graph: &G) -> Vec<usize> {
+    (0..graph.node_count()).filter(|&u| graph.degree(u) % 2 == 1).collect()
+}
+
+// Every edge once, from its smaller end; a self-loop is listed twice at its
+// node, so only every other one is kept.
+fn undirected_edges<G: GraphBase>(graph: &G) -> Vec<(usize, usize)> {
+    let mut edges = Vec::new();
+    for u in 0..graph.node_count() {
+        let mut half_loop = false;
+        for v in graph.adjacent(u) {
+            if u < v || (u == v && !half_loop) {
+                edges.push((u, v));
+            }
+            if u == v {
+                half_loop = !half_loop;
+            }
+        }
+    }
+    edges
+}
+
+// Iterative Hierholzer: follows unused edges from the top of the stack, and
+// emits a node once all its edges are used. Degrees must already allow a
+// walk from `start`; an edge left unused means the graph is disconnected.
+fn hierholzer(n_nodes: usize, edges: &[(usize, usize)], start: usize) -> Result<Vec<usize>, NotEulerian> {
+    // (neighbor, edge id) for every edge at each node
+    let mut incident = vec![Vec::new(); n_nodes];
+    for (id, &(u, v)) in edges.iter().enumerate() {
+        incident[u].push((v, id));
+        if u != v {
+            incident[v].push((u, id));
+        }
+    }
+    let mut used = vec![false; edges.len()];
+    let mut next = vec![0; n_nodes];
+    let mut stack = vec![start];
+    let mut walk = Vec::with_capacity(edges.len() + 1);
+    while let Some(&u) = stack.last() {
+        while next[u] < incident[u].len() && used[incident[u][next[u]].1] {
+            next[u] += 1;
+        }
+        match incident[u].get(next[u]) {
+            Some(&(v, id)) => {
+                used[id] = true;
+                stack.push(v);
+            }
+            None => {
+                walk.push(u);
+                stack.pop();
+            }
+        }
+    }
+    if let Some(id) = used.iter().position(|&u| !u) {
+        return Err(NotEulerian::Disconnected(start, edges[id].0));
+    }
+    walk.reverse();
+    Ok(walk)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{eulerian_circuit, eulerian_path, NotEulerian};
+    use test_util::{graph, Lcg};
+    use weighted_graph::WeightedGraph;
+
+    // Checks that `walk` uses every edge exactly once.
+    fn assert_uses_every_edge(edges: &[(usize, usize)], walk: &[usize]) {
+        assert_eq!(walk.len(), edges.len() + 1);
+        let mut left = edges.to_vec();
+        for step in walk.windows(2) {
+            let i = left.iter()
+                .position(|&(u, v)| (u, v) == (step[0], step[1]) || (v, u) == (step[0], step[1]))
+                .expect("walk follows an edge twice or a missing edge");
+            left.swap_remove(i);
+        }
+    }
+
+    #[test]
+    fn circuit() {
+        // Two triangles sharing node 2, a self-loop on 0 and a double edge 3-4.
+        let edges = [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2), (0, 0), (3, 4), (4, 3)];
+        let walk = eulerian_circuit(&graph(5, &edges)).unwrap();
+        assert_eq!(walk.first(), walk.last());
+        assert_uses_every_edge(&edges, &walk);
+        assert_eq!(eulerian_path(&graph(5, &edges)).unwrap().len(), edges.len() + 1);
+    }
+
+    #[test]
+    fn path() {
+        let edges = [(0, 1), (1, 2), (2, 0), (2, 3)];
+        assert_eq!(eulerian_circuit(&graph(4, &edges)), Err(NotEulerian::OddDegree(vec![2, 3])));
+        let walk = eulerian_path(&graph(4, &edges)).unwrap();
+        assert_eq!(walk[0], 2);
+        assert_eq!(walk[walk.len() - 1], 3);
+        assert_uses_every_edge(&edges, &walk);
+    }
+
+    #[test]
+    fn impossible() {
+        // A star with three leaves
+        let edges = [(0, 1), (0, 2), (0, 3)];
+        assert_eq!(eulerian_path(&graph(4, &edges)), Err(NotEulerian::OddDegree(vec![0, 1, 2, 3])));
+        // Two separate cycles; isolated nodes do not matter
+        let edges = [(0, 1), (1, 2), (2, 0), (4, 5), (5, 6), (6, 4)];
+        assert_eq!(eulerian_circuit(&graph(8, &edges)), Err(NotEulerian::Disconnected(0, 4)));
+        assert_eq!(eulerian_path(&graph(8, &edges)), Err(NotEulerian::Disconnected(0, 4)));
+    }
+
+    #[test]
+    fn without_edges() {
+        assert_eq!(eulerian_circuit(&graph(3, &[])), Ok(vec![]));
+        assert_eq!(eulerian_path(&graph(0, &[])), Ok(vec![]));
+        assert_eq!(eulerian_circuit(&graph(1, &[(0, 0)])), Ok(vec![0, 0]));
+    }
+
+    #[test]
+    fn weighted_graph() {
+        let mut g = WeightedGraph::new(3);
+        g.add_edge(0, 1, 4);
+        g.add_edge(1, 2, 1);
+        g.add_edge(2, 2, 3);
+        assert_eq!(eulerian_path(&g), Ok(vec![0, 1, 2, 2]));
+        g.add_edge(2, 0, 2);
+        assert_eq!(eulerian_circuit(&g), Ok(vec![0, 1, 2, 2, 0]));
+    }
+
+    #[test]
+    fn random_even_graphs() {
+        let mut rng = Lcg(11);
+        for _ in 0..50 {
+            // Closed random walks glued at node 0 keep every degree even.
+            let n = 1 + rng.next(20);
+            let mut edges = Vec::new();
+            for _ in 0..1 + rng.next(4) {
+                let mut u = 0;
+                for _ in 0..rng.next(15) {
+                    let v = rng.next(n);
+                    edges.push((u, v));
+                    u = v;
+                }
+                edges.push((u, 0));
+            }
+            let walk = eulerian_circuit(&graph(n, &edges)).unwrap();
+            assert_eq!(walk.first(), walk.last());
+            assert_uses_every_edge(&edges, &walk);
+        }
+    }
+}
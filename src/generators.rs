@@ -0,0 +1,280 @@
+// Graph generators. Every generator returns an `EdgeList` with unit weights,
+// to be turned into a graph with `to_weighted` or `to_undirected`. Random
+// generators take a seed: the same seed always gives the same graph.
+// Generated graphs have no self-loops or parallel edges.
+
+use std::ops::Range;
+
+use graph_io::EdgeList;
+
+// SplitMix64: small, fast, and good enough for test inputs.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Uniform in [0, 1).
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+fn unit_edges(n_nodes: usize, edges: Vec<(usize, usize)>) -> EdgeList {
+    EdgeList {
+        n_nodes,
+        edges: edges.into_iter().map(|(u, v)| (u, v, 1)).collect()
+    }
+}
+
+/// Every pair of nodes linked.
+pub fn complete(n: usize) -> EdgeList {
+    unit_edges(n, (0..n).flat_map(|v| (0..v).map(move |u| (u, v))).collect())
+}
+
+/// 0 - 1 - 2 - ... - (n - 1).
+pub fn path(n: usize) -> EdgeList {
+    unit_edges(n, (1..n).map(|v| (v - 1, v)).collect())
+}
+
+/// Node 0 linked to every other node.
+pub fn star(n: usize) -> EdgeList {
+    unit_edges(n, (1..n).map(|v| (0, v)).collect())
+}
+
+/// `rows` x `cols` grid: node `r * cols + c` is linked to its right and
+/// lower neighbors.
+pub fn grid(rows: usize, cols: usize) -> EdgeList {
+    let mut edges = Vec::new();
+    for r in 0..rows {
+        for c in 0..cols {
+            let u = r * cols + c;
+            if c + 1 < cols {
+                edges.push((u, u + 1));
+            }
+            if r + 1 < rows {
+                edges.push((u, u + cols));
+            }
+        }
+    }
+    unit_edges(rows * cols, edges)
+}
+
+/// Erdős–Rényi G(n, p): each pair of nodes is linked with probability `p`.
+/// Runs in O(n + m) by skipping over the pairs left out (Batagelj-Brandes).
+/// Panics if `p` is NaN.
+pub fn erdos_renyi(n: usize, p: f64, seed: u64) -> EdgeList {
+    assert!(!p.is_nan());
+    if p >= 1.0 {
+        return complete(n);
+    }
+    let mut edges = Vec::new();
+    if p <= 0.0 {
+        return unit_edges(n, edges);
+    }
+    let mut rng = Rng(seed);
+    let log_q = (-p).ln_1p();
+    // Walks the pairs (u, v) with u < v in order, v major.
+    let (mut u, mut v) = (0usize, 1usize);
+    let mut skip = ((1.0 - rng.unit()).ln() / log_q) as usize;
+    while v < n {
+        // A tiny p can make the skip overflow.
+        u = u.saturating_add(skip);
+        while u >= v && v < n {
+            u -= v;
+            v += 1;
+        }
+        if v < n {
+            edges.push((u, v));
+            u += 1;
+        }
+        skip = ((1.0 - rng.unit()).ln() / log_q) as usize;
+    }
+    unit_edges(n, edges)
+}
+
+/// Barabási–Albert preferential attachment: starts from a complete graph on
+/// `m + 1` nodes, then each new node links to `m` distinct earlier nodes,
+/// chosen with probability proportional to their degree.
+pub fn barabasi_albert(n: usize, m: usize, seed: u64) -> EdgeList {
+    assert!(m >= 1);
+    if n <= m + 1 {
+        return complete(n);
+    }
+    let mut list = complete(m + 1);
+    let mut rng = Rng(seed);
+    // Every node appears once per edge end, so a uniform pick is degree-biased.
+    let mut ends: Vec<usize> = list.edges.iter().flat_map(|&(u, v, _)| vec![u, v]).collect();
+    let mut targets = Vec::with_capacity(m);
+    for v in m + 1..n {
+        targets.clear();
+        while targets.len() < m {
+            let u = ends[rng.below(ends.len())];
+            if !targets.contains(&u) {
+                targets.push(u);
+            }
+        }
+        for &u in targets.iter() {
+            list.edges.push((u, v, 1));
+            ends.push(u);
+            ends.push(v);
+        }
+    }
+    list.n_nodes = n;
+    list
+}
+
+/// Tree drawn uniformly among the n^(n - 2) labelled trees on `n` nodes,
+/// by decoding a random Prüfer sequence in O(n).
+pub fn random_tree(n: usize, seed: u64) -> EdgeList {
+    if n <= 2 {
+        return path(n);
+    }
+    let mut rng = Rng(seed);
+    let sequence: Vec<usize> = (0..n - 2).map(|_| rng.below(n)).collect();
+    let mut degree = vec![1; n];
+    for &v in sequence.iter() {
+        degree[v] += 1;
+    }
+    let mut edges = Vec::with_capacity(n - 1);
+    let mut next = (0..n).find(|&u| degree[u] == 1).unwrap();
+    let mut leaf = next;
+    for &v in sequence.iter() {
+        edges.push((leaf, v));
+        degree[v] -= 1;
+        if degree[v] == 1 && v < next {
+            leaf = v;
+        } else {
+            next += 1;
+            while degree[next] != 1 {
+                next += 1;
+            }
+            leaf = next;
+        }
+    }
+    edges.push((leaf, n - 1));
+    unit_edges(n, edges)
+}
+
+/// Replaces every weight with one drawn uniformly from `range`.
+pub fn random_weights(list: &mut EdgeList, range: Range<i32>, seed: u64) {
+    assert!(range.start < range.end);
+    let mut rng = Rng(seed);
+    let span = (range.end as i64 - range.start as i64) as usize;
+    for edge in list.edges.iter_mut() {
+        edge.2 = (range.start as i64 + rng.below(span) as i64) as i32;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::{barabasi_albert, complete, erdos_renyi, grid, path, random_tree, random_weights, star};
+    use graph_io::EdgeList;
+    use undirected_graph::UndirectedGraph;
+
+    fn assert_simple(list: &EdgeList) {
+        let mut seen = HashSet::new();
+        for &(u, v, _) in list.edges.iter() {
+            assert!(u < list.n_nodes && v < list.n_nodes);
+            assert!(u != v, "self-loop on {}", u);
+            assert!(seen.insert((u.min(v), u.max(v))), "parallel edge {} - {}", u, v);
+        }
+    }
+
+    #[test]
+    fn fixed_shapes() {
+        assert_eq!(complete(4).edges.len(), 6);
+        assert_eq!(path(4).edges, vec![(0, 1, 1), (1, 2, 1), (2, 3, 1)]);
+        assert_eq!(star(4).edges, vec![(0, 1, 1), (0, 2, 1), (0, 3, 1)]);
+        let g = grid(2, 3);
+        assert_eq!(g.n_nodes, 6);
+        assert_eq!(g.edges.len(), 7);
+        for list in [complete(7), path(7), star(7), grid(3, 4), complete(0), path(1)].iter() {
+            assert_simple(list);
+        }
+        let s: UndirectedGraph<()> = grid(3, 4).to_undirected();
+        assert_eq!(s.shortest_unweighted_path(0, 11).unwrap().len(), 6);
+    }
+
+    #[test]
+    fn erdos_renyi_density() {
+        let n = 2000;
+        let list = erdos_renyi(n, 0.01, 1);
+        assert_simple(&list);
+        let expected = 0.01 * (n * (n - 1) / 2) as f64;
+        let m = list.edges.len() as f64;
+        assert!((m - expected).abs() < 0.05 * expected, "{} edges", m);
+        assert_eq!(erdos_renyi(10, 0.0, 1).edges.len(), 0);
+        assert_eq!(erdos_renyi(10, 1.0, 1).edges.len(), 45);
+        assert_eq!(erdos_renyi(1, 0.5, 1).edges.len(), 0);
+        assert_eq!(erdos_renyi(100, 1e-300, 1).edges.len(), 0);
+    }
+
+    #[test]
+    fn deterministic_seeds() {
+        assert_eq!(erdos_renyi(100, 0.1, 7), erdos_renyi(100, 0.1, 7));
+        assert!(erdos_renyi(100, 0.1, 7) != erdos_renyi(100, 0.1, 8));
+        assert_eq!(barabasi_albert(100, 3, 7), barabasi_albert(100, 3, 7));
+        assert_eq!(random_tree(100, 7), random_tree(100, 7));
+        assert!(random_tree(100, 7) != random_tree(100, 8));
+    }
+
+    #[test]
+    fn preferential_attachment() {
+        let (n, m) = (1000, 3);
+        let list = barabasi_albert(n, m, 2);
+        assert_simple(&list);
+        assert_eq!(list.n_nodes, n);
+        assert_eq!(list.edges.len(), m * (m + 1) / 2 + (n - m - 1) * m);
+        let s: UndirectedGraph<()> = list.to_undirected();
+        assert!(s.connected_components().iter().all(|&c| c == 0));
+        // Early nodes collect far more than the minimum degree.
+        assert!((0..n).map(|u| s.degree(u)).max().unwrap() > 10 * m);
+        assert_eq!(barabasi_albert(3, 5, 2), complete(3));
+    }
+
+    #[test]
+    fn random_trees() {
+        for n in 0..30 {
+            let list = random_tree(n, n as u64);
+            assert_simple(&list);
+            let s: UndirectedGraph<()> = list.to_undirected();
+            assert_eq!(s.is_tree(), n > 0);
+        }
+        // Every labelled tree on 4 nodes shows up (there are 4^2 = 16).
+        let trees: HashSet<Vec<(usize, usize, i32)>> = (0..2000)
+            .map(|seed| {
+                let mut edges: Vec<_> = random_tree(4, seed).edges.iter().map(|&(u, v, w)| (u.min(v), u.max(v), w)).collect();
+                edges.sort();
+                edges
+            })
+            .collect();
+        assert_eq!(trees.len(), 16);
+    }
+
+    #[test]
+    fn weights() {
+        let mut list = complete(30);
+        random_weights(&mut list, -5..5, 3);
+        assert!(list.edges.iter().all(|&(_, _, w)| (-5..5).contains(&w)));
+        assert!(list.edges.iter().any(|&(_, _, w)| w < 0));
+        random_weights(&mut list, i32::MIN..i32::MAX, 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn erdos_renyi_nan() {
+        erdos_renyi(10, f64::NAN, 1);
+    }
+}
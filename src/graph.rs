@@ -0,0 +1,474 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Structure shared by every graph representation: nodes are indexed
+/// `0..node_count()` and each node has a list of adjacent nodes.
+pub trait GraphBase {
+    type Adjacent<'a>: Iterator<Item = usize> where Self: 'a;
+
+    fn node_count(&self) -> usize;
+
+    /// Nodes adjacent to `u`, once per edge.
+    fn adjacent(&self, u: usize) -> Self::Adjacent<'_>;
+
+    fn degree(&self, u: usize) -> usize {
+        self.adjacent(u).count()
+    }
+
+    /// Whether `u` is a node, and not an index left vacant by a removal.
+    fn contains_node(&self, u: usize) -> bool {
+        u < self.node_count()
+    }
+}
+
+/// Why a checked graph mutation was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphError {
+    NodeOutOfRange { node: usize, size: usize },
+    /// The node was removed from a graph whose node indices are stable.
+    RemovedNode(usize),
+    SelfLoopForbidden(usize),
+    DuplicateEdge(usize, usize)
+}
+
+impl GraphError {
+    /// Ok if `node` is one of the `size` nodes of a graph.
+    pub fn check_node(node: usize, size: usize) -> Result<(), GraphError> {
+        if node < size {
+            Ok(())
+        } else {
+            Err(GraphError::NodeOutOfRange { node, size })
+        }
+    }
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GraphError::NodeOutOfRange { node, size } =>
+                write!(f, "node {} out of range for a graph of {} nodes", node, size),
+            GraphError::RemovedNode(u) => write!(f, "node {} was removed", u),
+            GraphError::SelfLoopForbidden(u) => write!(f, "self-loop on node {} not allowed", u),
+            GraphError::DuplicateEdge(u, v) => write!(f, "edge {} - {} already exists", u, v)
+        }
+    }
+}
+
+impl ::std::error::Error for GraphError {}
+
+/// Graphs whose edges carry an i32 weight.
+/// Unweighted graphs implement it with unit weights.
+pub trait WeightedEdges: GraphBase {
+    type WeightedAdjacent<'a>: Iterator<Item = (usize, i32)> where Self: 'a;
+
+    /// `(neighbor, weight)` pairs of `u`, once per edge.
+    fn weighted_adjacent(&self, u: usize) -> Self::WeightedAdjacent<'_>;
+}
+
+/// Depth-first traversal yielding node indices. A node is yielded once all
+/// the nodes reachable through it have been, so the deepest node comes first.
+pub struct Dfs<'a, G: GraphBase + 'a> {
+    graph: &'a G,
+    // Each node on the path from the start, with its remaining neighbors
+    stack: Vec<(usize, G::Adjacent<'a>)>,
+    visited: Vec<bool>
+}
+
+/// Breadth-first traversal yielding node indices.
+pub struct Bfs<'a, G: GraphBase + 'a> {
+    graph: &'a G,
+    queue: VecDeque<usize>,
+    visited: Vec<bool>
+}
+
+/// Breadth-first traversal yielding `(node, depth, parent)`, the parent
+/// being None for the start node.
+pub struct BfsTree<'a, G: GraphBase + 'a> {
+    graph: &'a G,
+    queue: VecDeque<(usize, usize, Option<usize>)>,
+    visited: Vec<bool>
+}
+
+/// Depth-first traversal yielding `(node, depth, parent)` in preorder,
+/// the parent being None for the start node.
+pub struct DfsTree<'a, G: GraphBase + 'a> {
+    graph: &'a G,
+    start: Option<usize>,
+    // Each node on the path from the start, with its depth and remaining neighbors
+    stack: Vec<(usize, usize, G::Adjacent<'a>)>,
+    visited: Vec<bool>
+}
+
+/// Event reported by `dfs_visit` and `undirected_dfs_visit`. Times come
+/// from a single clock ticking at every discovery and every finish, so the
+/// `[discover, finish]` intervals of two nodes are either nested or disjoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DfsEvent {
+    Discover { node: usize, time: usize },
+    Finish { node: usize, time: usize },
+    /// Edge to a node seen for the first time.
+    TreeEdge(usize, usize),
+    /// Edge to a node that is still being explored: an ancestor.
+    BackEdge(usize, usize),
+    /// Edge to an already finished descendant.
+    ForwardEdge(usize, usize),
+    /// Edge to an already finished node that is not a descendant.
+    CrossEdge(usize, usize)
+}
+
+/// Depth-first search from `start`, calling `visitor` on every event.
+/// Edges are followed as directed; see `undirected_dfs_visit` for graphs
+/// that list every edge from both of its ends.
+pub fn dfs_visit<G: GraphBase, F: FnMut(DfsEvent)>(graph: &G, start: usize, mut visitor: F) {
+    const UNSEEN: usize = usize::MAX;
+    let n = graph.node_count();
+    let mut discovered = vec![UNSEEN; n];
+    let mut finished = vec![false; n];
+    let mut time = 0;
+    discovered[start] = time;
+    visitor(DfsEvent::Discover { node: start, time });
+    time += 1;
+    let mut stack = vec![(start, graph.adjacent(start))];
+    while let Some(&mut (u, ref mut adjacent)) = stack.last_mut() {
+        match adjacent.next() {
+            Some(v) if discovered[v] == UNSEEN => {
+                visitor(DfsEvent::TreeEdge(u, v));
+                discovered[v] = time;
+                visitor(DfsEvent::Discover { node: v, time });
+                time += 1;
+                stack.push((v, graph.adjacent(v)));
+            }
+            Some(v) if !finished[v] => visitor(DfsEvent::BackEdge(u, v)),
+            Some(v) if discovered[v] > discovered[u] => visitor(DfsEvent::ForwardEdge(u, v)),
+            Some(v) => visitor(DfsEvent::CrossEdge(u, v)),
+            None => {
+                finished[u] = true;
+                visitor(DfsEvent::Finish { node: u, time });
+                time += 1;
+                stack.pop();
+            }
+        }
+    }
+}
+
+/// Depth-first search from `start` on an undirected graph, which lists
+/// every edge from both of its ends and a self-loop twice at its node.
+/// Each edge is reported once, as a tree edge or as a back edge from the
+/// descendant to the ancestor; there are no forward or cross edges.
+/// Parallel edges to the parent are back edges.
+pub fn undirected_dfs_visit<G: GraphBase, F: FnMut(DfsEvent)>(graph: &G, start: usize, mut visitor: F) {
+    const UNSEEN: usize = usize::MAX;
+    let n = graph.node_count();
+    let mut discovered = vec![UNSEEN; n];
+    let mut finished = vec![false; n];
+    let mut time = 0;
+    discovered[start] = time;
+    visitor(DfsEvent::Discover { node: start, time });
+    time += 1;
+    // Each node on the path, its remaining neighbors, its parent while the
+    // tree edge has not been skipped yet, and whether a self-loop is half seen
+    let mut stack = vec![(start, graph.adjacent(start), None, false)];
+    while let Some(&mut (u, ref mut adjacent, ref mut parent, ref mut half_loop)) = stack.last_mut() {
+        match adjacent.next() {
+            Some(v) if discovered[v] == UNSEEN => {
+                visitor(DfsEvent::TreeEdge(u, v));
+                discovered[v] = time;
+                visitor(DfsEvent::Discover { node: v, time });
+                time += 1;
+                stack.push((v, graph.adjacent(v), Some(u), false));
+            }
+            Some(v) if *parent == Some(v) => *parent = None,
+            Some(v) if v == u => {
+                if !*half_loop {
+                    visitor(DfsEvent::BackEdge(u, u));
+                }
+                *half_loop = !*half_loop;
+            }
+            Some(v) if !finished[v] => visitor(DfsEvent::BackEdge(u, v)),
+            // A back edge from a descendant, already reported from there
+            Some(_) => {}
+            None => {
+                finished[u] = true;
+                visitor(DfsEvent::Finish { node: u, time });
+                time += 1;
+                stack.pop();
+            }
+        }
+    }
+}
+
+impl<'a, G: GraphBase> Dfs<'a, G> {
+    pub fn new(graph: &'a G, start: usize) -> Dfs<'a, G> {
+        let mut visited = vec![false; graph.node_count()];
+        visited[start] = true;
+        Dfs {
+            graph,
+            stack: vec![(start, graph.adjacent(start))],
+            visited
+        }
+    }
+}
+
+impl<'a, G: GraphBase> Iterator for Dfs<'a, G> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        loop {
+            let next = {
+                let &mut (u, ref mut adjacent) = self.stack.last_mut()?;
+                let visited = &self.visited;
+                match adjacent.find(|&v| !visited[v]) {
+                    Some(v) => v,
+                    None => {
+                        self.stack.pop();
+                        return Some(u);
+                    }
+                }
+            };
+            self.visited[next] = true;
+            self.stack.push((next, self.graph.adjacent(next)));
+        }
+    }
+}
+
+impl<'a, G: GraphBase> Bfs<'a, G> {
+    pub fn new(graph: &'a G, start: usize) -> Bfs<'a, G> {
+        let mut visited = vec![false; graph.node_count()];
+        visited[start] = true;
+        Bfs {
+            graph,
+            queue: vec![start].into_iter().collect(),
+            visited
+        }
+    }
+}
+
+impl<'a, G: GraphBase> Iterator for Bfs<'a, G> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let u = self.queue.pop_front()?;
+        for v in self.graph.adjacent(u) {
+            if !self.visited[v] {
+                self.visited[v] = true;
+                self.queue.push_back(v);
+            }
+        }
+        Some(u)
+    }
+}
+
+impl<'a, G: GraphBase> BfsTree<'a, G> {
+    pub fn new(graph: &'a G, start: usize) -> BfsTree<'a, G> {
+        let mut visited = vec![false; graph.node_count()];
+        visited[start] = true;
+        BfsTree {
+            graph,
+            queue: vec![(start, 0, None)].into_iter().collect(),
+            visited
+        }
+    }
+}
+
+impl<'a, G: GraphBase> Iterator for BfsTree<'a, G> {
+    type Item = (usize, usize, Option<usize>);
+
+    fn next(&mut self) -> Option<(usize, usize, Option<usize>)> {
+        let (u, depth, parent) = self.queue.pop_front()?;
+        for v in self.graph.adjacent(u) {
+            if !self.visited[v] {
+                self.visited[v] = true;
+                self.queue.push_back((v, depth + 1, Some(u)));
+            }
+        }
+        Some((u, depth, parent))
+    }
+}
+
+impl<'a, G: GraphBase> DfsTree<'a, G> {
+    pub fn new(graph: &'a G, start: usize) -> DfsTree<'a, G> {
+        let mut visited = vec![false; graph.node_count()];
+        visited[start] = true;
+        DfsTree {
+            graph,
+            start: Some(start),
+            stack: Vec::new(),
+            visited
+        }
+    }
+}
+
+impl<'a, G: GraphBase> Iterator for DfsTree<'a, G> {
+    type Item = (usize, usize, Option<usize>);
+
+    fn next(&mut self) -> Option<(usize, usize, Option<usize>)> {
+        if let Some(start) = self.start.take() {
+            self.stack.push((start, 0, self.graph.adjacent(start)));
+            return Some((start, 0, None));
+        }
+        loop {
+            let (u, depth, v) = {
+                let &mut (u, depth, ref mut adjacent) = self.stack.last_mut()?;
+                let visited = &self.visited;
+                (u, depth, adjacent.find(|&v| !visited[v]))
+            };
+            match v {
+                Some(v) => {
+                    self.visited[v] = true;
+                    self.stack.push((v, depth + 1, self.graph.adjacent(v)));
+                    return Some((v, depth + 1, Some(u)));
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{undirected_dfs_visit, Bfs, BfsTree, Dfs, DfsEvent, DfsTree, GraphBase, WeightedEdges};
+    use connectivity;
+    use generators;
+    use shortest_paths;
+    use undirected_graph::UndirectedGraph;
+    use weighted_graph::WeightedGraph;
+
+    const EDGES: [(usize, usize, i32); 5] = [(0, 1, 7), (0, 2, 1), (1, 3, 1), (3, 4, 2), (2, 3, 1)];
+
+    fn weighted_sample() -> WeightedGraph {
+        let mut g = WeightedGraph::new(5);
+        for &(u, v, weight) in EDGES.iter() {
+            g.add_edge(u, v, weight);
+        }
+        g
+    }
+
+    #[test]
+    fn traversals_on_weighted_graph() {
+        let g = weighted_sample();
+        assert_eq!(Bfs::new(&g, 0).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(Dfs::new(&g, 0).collect::<Vec<_>>(), vec![4, 2, 3, 1, 0]);
+        assert_eq!(g.degree(3), 3);
+    }
+
+    #[test]
+    fn traversal_trees() {
+        let g = weighted_sample();
+        assert_eq!(BfsTree::new(&g, 4).collect::<Vec<_>>(), vec![
+            (4, 0, None), (3, 1, Some(4)), (1, 2, Some(3)), (2, 2, Some(3)), (0, 3, Some(1))
+        ]);
+        assert_eq!(DfsTree::new(&g, 0).collect::<Vec<_>>(), vec![
+            (0, 0, None), (1, 1, Some(0)), (3, 2, Some(1)), (4, 3, Some(3)), (2, 3, Some(3))
+        ]);
+        // Same nodes as the post-order traversal
+        let mut preorder: Vec<usize> = DfsTree::new(&g, 2).map(|(u, _, _)| u).collect();
+        let mut postorder: Vec<usize> = Dfs::new(&g, 2).collect();
+        preorder.sort();
+        postorder.sort();
+        assert_eq!(preorder, postorder);
+    }
+
+    #[test]
+    fn visitor_on_undirected_graph() {
+        let mut s = UndirectedGraph::<()>::new(4);
+        s.add_edge(0, 1);
+        s.add_edge(1, 2);
+        s.add_edge(2, 0);
+        s.add_edge(2, 2);
+        s.add_edge(0, 1);
+        let mut events = Vec::new();
+        undirected_dfs_visit(&s, 0, |e| events.push(e));
+        assert_eq!(events, vec![
+            DfsEvent::Discover { node: 0, time: 0 },
+            DfsEvent::TreeEdge(0, 1),
+            DfsEvent::Discover { node: 1, time: 1 },
+            DfsEvent::TreeEdge(1, 2),
+            DfsEvent::Discover { node: 2, time: 2 },
+            DfsEvent::BackEdge(2, 0),
+            DfsEvent::BackEdge(2, 2),
+            DfsEvent::Finish { node: 2, time: 3 },
+            DfsEvent::BackEdge(1, 0),
+            DfsEvent::Finish { node: 1, time: 4 },
+            DfsEvent::Finish { node: 0, time: 5 }
+        ]);
+    }
+
+    // Tarjan's low links from the events alone: a tree edge u-v is a bridge
+    // when no back edge from the subtree of v reaches u or above.
+    fn bridges_from_events<G: GraphBase>(graph: &G) -> Vec<(usize, usize)> {
+        let n = graph.node_count();
+        let mut discovered = vec![usize::MAX; n];
+        let mut low = vec![usize::MAX; n];
+        let mut parent = vec![usize::MAX; n];
+        let mut bridges = Vec::new();
+        for root in 0..n {
+            if discovered[root] != usize::MAX {
+                continue;
+            }
+            undirected_dfs_visit(graph, root, |e| match e {
+                DfsEvent::Discover { node, time } => {
+                    discovered[node] = time;
+                    low[node] = time;
+                }
+                DfsEvent::TreeEdge(u, v) => parent[v] = u,
+                DfsEvent::BackEdge(u, v) => low[u] = low[u].min(discovered[v]),
+                DfsEvent::Finish { node, .. } if node != root => {
+                    let p = parent[node];
+                    low[p] = low[p].min(low[node]);
+                    if low[node] > discovered[p] {
+                        bridges.push((p.min(node), p.max(node)));
+                    }
+                }
+                _ => {}
+            });
+        }
+        bridges.sort();
+        bridges
+    }
+
+    #[test]
+    fn visitor_events_give_bridges() {
+        for seed in 0..50 {
+            let n = 1 + seed as usize;
+            let mut s: UndirectedGraph<()> = generators::erdos_renyi(n, 2.0 / n as f64, seed).to_undirected();
+            // A parallel edge and a self-loop are never bridges.
+            s.add_edge(0, n / 2);
+            s.add_edge(0, n / 2);
+            s.add_edge(n - 1, n - 1);
+            let mut expected = connectivity::biconnectivity(&s).bridges;
+            expected.sort();
+            assert_eq!(bridges_from_events(&s), expected);
+        }
+    }
+
+    #[test]
+    fn traversals_on_every_representation() {
+        let g = weighted_sample();
+        let mut s = UndirectedGraph::<()>::new(5);
+        for &(u, v, _) in EDGES.iter() {
+            s.add_edge(u, v);
+        }
+        let csr = g.freeze();
+        for start in 0..5 {
+            let bfs: Vec<usize> = Bfs::new(&g, start).collect();
+            assert_eq!(Bfs::new(&s, start).collect::<Vec<_>>(), bfs);
+            assert_eq!(Bfs::new(&csr, start).collect::<Vec<_>>(), bfs);
+            let dfs: Vec<usize> = Dfs::new(&g, start).collect();
+            assert_eq!(Dfs::new(&s, start).collect::<Vec<_>>(), dfs);
+            assert_eq!(Dfs::new(&csr, start).collect::<Vec<_>>(), dfs);
+        }
+    }
+
+    #[test]
+    fn dijkstra_on_unit_weight_graph() {
+        let mut s = UndirectedGraph::<()>::new(5);
+        s.add_edge(0, 1);
+        s.add_edge(1, 2);
+        s.add_edge(0, 3);
+        assert_eq!(s.weighted_adjacent(1).collect::<Vec<_>>(), vec![(0, 1), (2, 1)]);
+        assert_eq!(shortest_paths::dijkstra(&s, 2), vec![2, 1, 0, 3, i32::MAX]);
+        assert_eq!(shortest_paths::floyd(&s).get_shortest_path(3, 2), 3);
+    }
+}
@@ -0,0 +1,542 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use undirected_graph::UndirectedGraph;
+use weighted_graph::WeightedGraph;
+
+/// Undirected edges `(u, v, weight)` over `n_nodes` nodes, the common ground
+/// between file formats and graph types.
+/// Unweighted graphs use a weight of 1.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EdgeList {
+    pub n_nodes: usize,
+    pub edges: Vec<(usize, usize, i32)>
+}
+
+/// Largest node count the readers accept, so that a bad header cannot make
+/// a graph allocate more than a few hundred megabytes.
+pub const MAX_NODES: usize = 1 << 24;
+
+#[derive(Debug)]
+pub struct ParseError {
+    /// 1-based line number where the error was found.
+    pub line: usize,
+    pub kind: ParseErrorKind
+}
+
+#[derive(Debug)]
+pub enum ParseErrorKind {
+    Io(io::Error),
+    /// The header (DIMACS problem line, Matrix Market banner or size line) is missing.
+    MissingHeader,
+    InvalidHeader(String),
+    /// A line that does not belong to the format.
+    UnexpectedLine(String),
+    InvalidNumber(String),
+    /// Weights must be integers that fit in an i32.
+    InvalidWeight(String),
+    NodeOutOfRange(usize),
+    /// More nodes than `MAX_NODES`.
+    TooManyNodes(usize),
+    EdgeCountMismatch { expected: usize, found: usize }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match self.kind {
+            ParseErrorKind::Io(ref e) => write!(f, "{}", e),
+            ParseErrorKind::MissingHeader => write!(f, "missing header"),
+            ParseErrorKind::InvalidHeader(ref s) => write!(f, "invalid header `{}`", s),
+            ParseErrorKind::UnexpectedLine(ref s) => write!(f, "unexpected line `{}`", s),
+            ParseErrorKind::InvalidNumber(ref s) => write!(f, "invalid number `{}`", s),
+            ParseErrorKind::InvalidWeight(ref s) => write!(f, "invalid weight `{}`", s),
+            ParseErrorKind::NodeOutOfRange(u) => write!(f, "node {} out of range", u),
+            ParseErrorKind::TooManyNodes(n) => write!(f, "{} nodes, more than the limit of {}", n, MAX_NODES),
+            ParseErrorKind::EdgeCountMismatch { expected, found } => {
+                write!(f, "expected {} edges, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl EdgeList {
+    pub fn from_weighted(graph: &WeightedGraph) -> EdgeList {
+        let mut edges = Vec::new();
+        for u in 0..graph.size() {
+            // Each edge is stored on both ends, self-loops twice on the same node.
+            let mut self_loop = false;
+            for (v, weight) in graph.neighbors(u) {
+                if u < v || (u == v && !self_loop) {
+                    edges.push((u, v, weight));
+                }
+                if u == v {
+                    self_loop = !self_loop;
+                }
+            }
+        }
+        EdgeList { n_nodes: graph.size(), edges }
+    }
+
+    /// Edges of an undirected graph. Removed nodes are kept as isolated
+    /// nodes so that the other indices do not change.
+    pub fn from_undirected<T, E>(graph: &UndirectedGraph<T, E>) -> EdgeList {
+        let mut edges = Vec::new();
+        for u in 0..graph.size() {
+            let mut self_loop = false;
+            for &v in graph.from(u) {
+                if u < v || (u == v && !self_loop) {
+                    edges.push((u, v, 1));
+                }
+                if u == v {
+                    self_loop = !self_loop;
+                }
+            }
+        }
+        EdgeList { n_nodes: graph.size(), edges }
+    }
+
+    pub fn to_weighted(&self) -> WeightedGraph {
+        let mut g = WeightedGraph::new(self.n_nodes);
+        for &(u, v, weight) in self.edges.iter() {
+            g.add_edge(u, v, weight);
+        }
+        g
+    }
+
+    /// Builds an `UndirectedGraph` without node data. Weights are dropped.
+    pub fn to_undirected<T: Clone>(&self) -> UndirectedGraph<T> {
+        let mut g = UndirectedGraph::new(self.n_nodes);
+        for &(u, v, _) in self.edges.iter() {
+            g.add_edge(u, v);
+        }
+        g
+    }
+
+    /// Reads a DIMACS shortest path (`.gr`) file.
+    /// Arcs are directed in DIMACS: an arc and its reverse arc with the same
+    /// weight make one undirected edge, any other arc is an edge on its own.
+    pub fn read_dimacs<R: BufRead>(reader: R) -> Result<EdgeList, ParseError> {
+        let mut header: Option<(usize, usize)> = None;
+        let mut arcs = Vec::new();
+        let mut last_line = 0;
+        for (i, line) in reader.lines().enumerate() {
+            let line_no = i + 1;
+            last_line = line_no;
+            let line = line.map_err(|e| error(line_no, ParseErrorKind::Io(e)))?;
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.first() {
+                None | Some(&"c") => continue,
+                Some(&"p") => {
+                    if header.is_some() || fields.len() != 4 || fields[1] != "sp" {
+                        return Err(error(line_no, ParseErrorKind::InvalidHeader(line.clone())));
+                    }
+                    header = Some((parse_node_count(line_no, fields[2])?, parse(line_no, fields[3])?));
+                }
+                Some(&"a") => {
+                    let n_nodes = match header {
+                        Some((n_nodes, _)) => n_nodes,
+                        None => return Err(error(line_no, ParseErrorKind::MissingHeader))
+                    };
+                    if fields.len() != 4 {
+                        return Err(error(line_no, ParseErrorKind::UnexpectedLine(line.clone())));
+                    }
+                    let u = parse_node(line_no, fields[1], n_nodes, 1)?;
+                    let v = parse_node(line_no, fields[2], n_nodes, 1)?;
+                    arcs.push((u, v, parse_weight(line_no, fields[3])?));
+                }
+                Some(_) => return Err(error(line_no, ParseErrorKind::UnexpectedLine(line.clone())))
+            }
+        }
+        let (n_nodes, n_arcs) = match header {
+            Some(h) => h,
+            None => return Err(error(last_line, ParseErrorKind::MissingHeader))
+        };
+        if arcs.len() != n_arcs {
+            return Err(error(last_line, ParseErrorKind::EdgeCountMismatch { expected: n_arcs, found: arcs.len() }));
+        }
+        Ok(EdgeList { n_nodes, edges: pair_arcs(&arcs) })
+    }
+
+    /// Writes a DIMACS shortest path file, with one arc per direction of each edge.
+    pub fn write_dimacs<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "p sp {} {}", self.n_nodes, 2 * self.edges.len())?;
+        for &(u, v, weight) in self.edges.iter() {
+            writeln!(writer, "a {} {} {}", u + 1, v + 1, weight)?;
+            writeln!(writer, "a {} {} {}", v + 1, u + 1, weight)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a plain edge list: one `u v [weight]` line per edge, 0-based
+    /// node indices, weight 1 when omitted. Lines starting with `#` or `%`
+    /// are comments. The node count is the largest index plus one.
+    pub fn read_edge_list<R: BufRead>(reader: R) -> Result<EdgeList, ParseError> {
+        let mut edges = Vec::new();
+        let mut n_nodes = 0;
+        for (i, line) in reader.lines().enumerate() {
+            let line_no = i + 1;
+            let line = line.map_err(|e| error(line_no, ParseErrorKind::Io(e)))?;
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.is_empty() || fields[0].starts_with('#') || fields[0].starts_with('%') {
+                continue;
+            }
+            if fields.len() < 2 || fields.len() > 3 {
+                return Err(error(line_no, ParseErrorKind::UnexpectedLine(line.clone())));
+            }
+            let u: usize = parse(line_no, fields[0])?;
+            let v: usize = parse(line_no, fields[1])?;
+            let weight = match fields.get(2) {
+                Some(w) => parse_weight(line_no, w)?,
+                None => 1
+            };
+            match u.max(v).checked_add(1) {
+                Some(n) if n <= MAX_NODES => n_nodes = n_nodes.max(n),
+                _ => return Err(error(line_no, ParseErrorKind::TooManyNodes(u.max(v).saturating_add(1))))
+            }
+            edges.push((u, v, weight));
+        }
+        Ok(EdgeList { n_nodes, edges })
+    }
+
+    /// Writes a plain edge list. Isolated nodes with the largest indices are lost.
+    pub fn write_edge_list<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for &(u, v, weight) in self.edges.iter() {
+            writeln!(writer, "{} {} {}", u, v, weight)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a Matrix Market coordinate file holding a square adjacency matrix.
+    /// `symmetric` matrices give one edge per entry, `general` ones are
+    /// paired like DIMACS arcs. `pattern` entries have weight 1.
+    pub fn read_matrix_market<R: BufRead>(reader: R) -> Result<EdgeList, ParseError> {
+        let mut lines = reader.lines().enumerate();
+        let banner = match lines.next() {
+            Some((_, line)) => line.map_err(|e| error(1, ParseErrorKind::Io(e)))?,
+            None => return Err(error(1, ParseErrorKind::MissingHeader))
+        };
+        let fields: Vec<String> = banner.split_whitespace().map(|f| f.to_lowercase()).collect();
+        if fields.is_empty() || fields[0] != "%%matrixmarket" {
+            return Err(error(1, ParseErrorKind::MissingHeader));
+        }
+        if fields.len() != 5 || fields[1] != "matrix" || fields[2] != "coordinate" {
+            return Err(error(1, ParseErrorKind::InvalidHeader(banner.clone())));
+        }
+        let pattern = match fields[3].as_str() {
+            "pattern" => true,
+            "integer" | "real" => false,
+            _ => return Err(error(1, ParseErrorKind::InvalidHeader(banner.clone())))
+        };
+        let symmetric = match fields[4].as_str() {
+            "symmetric" => true,
+            "general" => false,
+            _ => return Err(error(1, ParseErrorKind::InvalidHeader(banner.clone())))
+        };
+
+        let mut size: Option<(usize, usize)> = None;
+        let mut entries = Vec::new();
+        let mut last_line = 1;
+        for (i, line) in lines {
+            let line_no = i + 1;
+            last_line = line_no;
+            let line = line.map_err(|e| error(line_no, ParseErrorKind::Io(e)))?;
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.is_empty() || fields[0].starts_with('%') {
+                continue;
+            }
+            let (n_nodes, _) = match size {
+                Some(size) => size,
+                None => {
+                    if fields.len() != 3 {
+                        return Err(error(line_no, ParseErrorKind::InvalidHeader(line.clone())));
+                    }
+                    let rows = parse_node_count(line_no, fields[0])?;
+                    let cols: usize = parse(line_no, fields[1])?;
+                    if rows != cols {
+                        return Err(error(line_no, ParseErrorKind::InvalidHeader(line.clone())));
+                    }
+                    size = Some((rows, parse(line_no, fields[2])?));
+                    continue;
+                }
+            };
+            if fields.len() != if pattern { 2 } else { 3 } {
+                return Err(error(line_no, ParseErrorKind::UnexpectedLine(line.clone())));
+            }
+            let u = parse_node(line_no, fields[0], n_nodes, 1)?;
+            let v = parse_node(line_no, fields[1], n_nodes, 1)?;
+            let weight = if pattern { 1 } else { parse_weight(line_no, fields[2])? };
+            entries.push((u, v, weight));
+        }
+        let (n_nodes, n_entries) = match size {
+            Some(size) => size,
+            None => return Err(error(last_line, ParseErrorKind::MissingHeader))
+        };
+        if entries.len() != n_entries {
+            return Err(error(last_line, ParseErrorKind::EdgeCountMismatch { expected: n_entries, found: entries.len() }));
+        }
+        let edges = if symmetric { entries } else { pair_arcs(&entries) };
+        Ok(EdgeList { n_nodes, edges })
+    }
+
+    /// Writes a symmetric integer Matrix Market coordinate file.
+    pub fn write_matrix_market<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "%%MatrixMarket matrix coordinate integer symmetric")?;
+        writeln!(writer, "{} {} {}", self.n_nodes, self.n_nodes, self.edges.len())?;
+        for &(u, v, weight) in self.edges.iter() {
+            // Symmetric files store the lower triangle.
+            writeln!(writer, "{} {} {}", u.max(v) + 1, u.min(v) + 1, weight)?;
+        }
+        Ok(())
+    }
+}
+
+fn error(line: usize, kind: ParseErrorKind) -> ParseError {
+    ParseError { line, kind }
+}
+
+fn parse<T: ::std::str::FromStr>(line: usize, field: &str) -> Result<T, ParseError> {
+    field.parse().map_err(|_| error(line, ParseErrorKind::InvalidNumber(field.to_string())))
+}
+
+fn parse_node_count(line: usize, field: &str) -> Result<usize, ParseError> {
+    let n: usize = parse(line, field)?;
+    if n > MAX_NODES {
+        return Err(error(line, ParseErrorKind::TooManyNodes(n)));
+    }
+    Ok(n)
+}
+
+// Parses a node index numbered from `base` into a 0-based index below `n_nodes`.
+fn parse_node(line: usize, field: &str, n_nodes: usize, base: usize) -> Result<usize, ParseError> {
+    let u: usize = parse(line, field)?;
+    if u < base || u - base >= n_nodes {
+        return Err(error(line, ParseErrorKind::NodeOutOfRange(u)));
+    }
+    Ok(u - base)
+}
+
+// Accepts integral reals such as `3.0`, which Matrix Market tools often write.
+fn parse_weight(line: usize, field: &str) -> Result<i32, ParseError> {
+    if let Ok(w) = field.parse::<i32>() {
+        return Ok(w);
+    }
+    match field.parse::<f64>() {
+        Ok(w) if w.fract() == 0.0 && w >= i32::MIN as f64 && w <= i32::MAX as f64 => Ok(w as i32),
+        _ => Err(error(line, ParseErrorKind::InvalidWeight(field.to_string())))
+    }
+}
+
+// Merges each arc with a matching reverse arc into one undirected edge.
+fn pair_arcs(arcs: &[(usize, usize, i32)]) -> Vec<(usize, usize, i32)> {
+    let mut unpaired: HashMap<(usize, usize, i32), usize> = HashMap::new();
+    let mut edges = Vec::new();
+    for &(u, v, weight) in arcs.iter() {
+        match unpaired.get_mut(&(v, u, weight)) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                continue;
+            }
+            _ => {}
+        }
+        *unpaired.entry((u, v, weight)).or_insert(0) += 1;
+        edges.push((u, v, weight));
+    }
+    edges
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EdgeList, ParseErrorKind, MAX_NODES};
+    use test_util::sample_graph;
+    use undirected_graph::UndirectedGraph;
+    use weighted_graph::WeightedGraph;
+
+    // The sample graph with a self-loop and a parallel edge, which every
+    // format must keep, and an isolated last node.
+    fn multigraph() -> WeightedGraph {
+        let mut g = sample_graph();
+        g.add_edge(3, 3, 5);
+        g.add_edge(0, 1, 6);
+        g.add_node();
+        g
+    }
+
+    fn assert_same_graph(a: &WeightedGraph, b: &WeightedGraph) {
+        assert_eq!(a.size(), b.size());
+        for u in 0..a.size() {
+            let mut na: Vec<_> = a.neighbors(u).collect();
+            let mut nb: Vec<_> = b.neighbors(u).collect();
+            na.sort();
+            nb.sort();
+            assert_eq!(na, nb);
+        }
+    }
+
+    #[test]
+    fn edge_list_from_weighted() {
+        let list = EdgeList::from_weighted(&multigraph());
+        assert_eq!(list.n_nodes, 11);
+        assert_eq!(list.edges.len(), 19);
+        assert_eq!(&list.edges[..3], &[(0, 1, 4), (0, 5, 1), (0, 1, 6)]);
+        assert_eq!(list.edges.iter().filter(|&&(u, v, _)| u == v).collect::<Vec<_>>(), vec![&(3, 3, 5)]);
+        assert_same_graph(&list.to_weighted(), &multigraph());
+    }
+
+    #[test]
+    fn edge_list_from_undirected() {
+        let mut g = UndirectedGraph::<i32>::new(3);
+        g.add_edge(0, 1);
+        g.add_edge(2, 1);
+        g.add_edge(2, 2);
+        let list = EdgeList::from_undirected(&g);
+        assert_eq!(list.edges, vec![(0, 1, 1), (1, 2, 1), (2, 2, 1)]);
+
+        let h = list.to_undirected::<i32>();
+        assert_eq!(h.from(1), &[0, 2]);
+        assert_eq!(h.from(2), &[1, 2, 2]);
+    }
+
+    #[test]
+    fn dimacs_round_trip() {
+        let list = EdgeList::from_weighted(&multigraph());
+        let mut out = Vec::new();
+        list.write_dimacs(&mut out).unwrap();
+        let read = EdgeList::read_dimacs(&out[..]).unwrap();
+        assert_same_graph(&read.to_weighted(), &multigraph());
+    }
+
+    #[test]
+    fn read_dimacs() {
+        let file = "c road network\n\
+                    p sp 3 3\n\
+                    a 1 2 5\n\
+                    c unpaired arc\n\
+                    a 2 3 1\n\
+                    a 2 1 5\n";
+        let list = EdgeList::read_dimacs(file.as_bytes()).unwrap();
+        assert_eq!(list.n_nodes, 3);
+        assert_eq!(list.edges, vec![(0, 1, 5), (1, 2, 1)]);
+    }
+
+    #[test]
+    fn dimacs_errors() {
+        let err = EdgeList::read_dimacs("a 1 2 3\n".as_bytes()).unwrap_err();
+        assert_eq!(err.line, 1);
+        assert!(matches!(err.kind, ParseErrorKind::MissingHeader));
+
+        let err = EdgeList::read_dimacs("p sp 2 1\nc\na 1 3 1\n".as_bytes()).unwrap_err();
+        assert_eq!(err.line, 3);
+        assert!(matches!(err.kind, ParseErrorKind::NodeOutOfRange(3)));
+
+        let err = EdgeList::read_dimacs("p sp 2 1\na 1 x 1\n".as_bytes()).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(matches!(err.kind, ParseErrorKind::InvalidNumber(_)));
+
+        let err = EdgeList::read_dimacs("p sp 2 2\na 1 2 1\n".as_bytes()).unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::EdgeCountMismatch { expected: 2, found: 1 }));
+
+        let err = EdgeList::read_dimacs("p max 2 2\n".as_bytes()).unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::InvalidHeader(_)));
+
+        let err = EdgeList::read_dimacs("p sp 2 1\ne 1 2\n".as_bytes()).unwrap_err();
+        assert_eq!(format!("{}", err), "line 2: unexpected line `e 1 2`");
+
+        let err = EdgeList::read_dimacs("p sp 1000000000000 0\n".as_bytes()).unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::TooManyNodes(1_000_000_000_000)));
+    }
+
+    #[test]
+    fn edge_list_round_trip() {
+        let list = EdgeList::from_weighted(&multigraph());
+        let mut out = Vec::new();
+        list.write_edge_list(&mut out).unwrap();
+        let read = EdgeList::read_edge_list(&out[..]).unwrap();
+        assert_eq!(read.n_nodes, 10);
+        assert_eq!(read.edges, list.edges);
+    }
+
+    #[test]
+    fn read_edge_list() {
+        let file = "# social graph\n0 1\n\n1 4 -2\n% other comment\n";
+        let list = EdgeList::read_edge_list(file.as_bytes()).unwrap();
+        assert_eq!(list.n_nodes, 5);
+        assert_eq!(list.edges, vec![(0, 1, 1), (1, 4, -2)]);
+
+        let err = EdgeList::read_edge_list("0 1\n1 2 3 4\n".as_bytes()).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(matches!(err.kind, ParseErrorKind::UnexpectedLine(_)));
+
+        let err = EdgeList::read_edge_list("0 1 1.5\n".as_bytes()).unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::InvalidWeight(_)));
+
+        let file = format!("0 1\n{} 0\n", usize::MAX);
+        let err = EdgeList::read_edge_list(file.as_bytes()).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(matches!(err.kind, ParseErrorKind::TooManyNodes(usize::MAX)));
+        let file = format!("0 {}\n", MAX_NODES);
+        assert!(matches!(EdgeList::read_edge_list(file.as_bytes()).unwrap_err().kind, ParseErrorKind::TooManyNodes(_)));
+    }
+
+    #[test]
+    fn matrix_market_round_trip() {
+        let list = EdgeList::from_weighted(&multigraph());
+        let mut out = Vec::new();
+        list.write_matrix_market(&mut out).unwrap();
+        let read = EdgeList::read_matrix_market(&out[..]).unwrap();
+        assert_same_graph(&read.to_weighted(), &multigraph());
+    }
+
+    #[test]
+    fn read_matrix_market() {
+        let file = "%%MatrixMarket matrix coordinate real general\n\
+                    % comment\n\
+                    3 3 3\n\
+                    1 2 2.0\n\
+                    2 1 2\n\
+                    3 1 4e0\n";
+        let list = EdgeList::read_matrix_market(file.as_bytes()).unwrap();
+        assert_eq!(list.n_nodes, 3);
+        assert_eq!(list.edges, vec![(0, 1, 2), (2, 0, 4)]);
+
+        let file = "%%MatrixMarket matrix coordinate pattern symmetric\n2 2 1\n2 1\n";
+        let list = EdgeList::read_matrix_market(file.as_bytes()).unwrap();
+        assert_eq!(list.edges, vec![(1, 0, 1)]);
+    }
+
+    #[test]
+    fn matrix_market_errors() {
+        let err = EdgeList::read_matrix_market("3 3 0\n".as_bytes()).unwrap_err();
+        assert_eq!(err.line, 1);
+        assert!(matches!(err.kind, ParseErrorKind::MissingHeader));
+
+        let err = EdgeList::read_matrix_market("%%MatrixMarket matrix array real general\n".as_bytes()).unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::InvalidHeader(_)));
+
+        let file = "%%MatrixMarket matrix coordinate integer symmetric\n2 3 0\n";
+        let err = EdgeList::read_matrix_market(file.as_bytes()).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(matches!(err.kind, ParseErrorKind::InvalidHeader(_)));
+
+        let file = "%%MatrixMarket matrix coordinate integer symmetric\n2 2 1\n0 1 1\n";
+        let err = EdgeList::read_matrix_market(file.as_bytes()).unwrap_err();
+        assert_eq!(err.line, 3);
+        assert!(matches!(err.kind, ParseErrorKind::NodeOutOfRange(0)));
+
+        let file = "%%MatrixMarket matrix coordinate integer symmetric\n2 2 2\n2 1 1\n";
+        let err = EdgeList::read_matrix_market(file.as_bytes()).unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::EdgeCountMismatch { expected: 2, found: 1 }));
+
+        let file = format!("%%MatrixMarket matrix coordinate pattern general\n{0} {0} 0\n", MAX_NODES + 1);
+        let err = EdgeList::read_matrix_market(file.as_bytes()).unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::TooManyNodes(_)));
+    }
+}
@@ -0,0 +1,251 @@
+use std::fmt;
+
+use graph::WeightedEdges;
+
+/// Largest graph accepted by the Held-Karp solvers, which need
+/// 2^n * n * 8 bytes: 160 MiB at 20 nodes.
+pub const HELD_KARP_MAX_NODES: usize = 20;
+
+/// The graph has more than `HELD_KARP_MAX_NODES` nodes, given here.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TooManyNodes(pub usize);
+
+impl fmt::Display for TooManyNodes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Held-Karp needs at most {} nodes, got {}", HELD_KARP_MAX_NODES, self.0)
+    }
+}
+
+impl ::std::error::Error for TooManyNodes {}
+
+/// Walk through every node exactly once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tour {
+    /// Sum of the weights of the edges taken, including the edge back to
+    /// the first node for a cycle.
+    pub cost: i64,
+    /// Every node once, in the order visited.
+    pub nodes: Vec<usize>
+}
+
+/// Cheapest path through every node exactly once (Held-Karp), in
+/// O(2^n * n^2), or None if there is none or the graph has no nodes.
+/// Any weights are allowed; self-loops are ignored. Fails beyond
+/// HELD_KARP_MAX_NODES nodes.
+pub fn shortest_hamiltonian_path<G: WeightedEdges>(graph: &G) -> Result<Option<Tour>, TooManyNodes> {
+    let n = graph.node_count();
+    let table = HeldKarp::compute(graph, 0..n)?;
+    let full = (1 << n) - 1;
+    let best = (0..n).filter_map(|v| Some((table.cost(full, v)?, v))).min();
+    Ok(best.map(|(cost, last)| Tour { cost, nodes: table.path(full, last) }))
+}
+
+/// Cheapest cycle through every node exactly once (Held-Karp), the
+/// travelling salesman tour, in O(2^n * n^2), or None if there is none.
+/// The tour starts at node 0. With two nodes it goes there and back along
+/// the same edge, and a single node needs a self-loop. Fails beyond
+/// HELD_KARP_MAX_NODES nodes.
+pub fn shortest_hamiltonian_cycle<G: WeightedEdges>(graph: &G) -> Result<Option<Tour>, TooManyNodes> {
+    let n = graph.node_count();
+    let table = HeldKarp::compute(graph, 0..1.min(n))?;
+    let full = (1 << n) - 1;
+    let best = (0..n)
+        .filter_map(|v| Some((table.cost(full, v)? + table.weight(v, 0)? as i64, v)))
+        .min();
+    Ok(best.map(|(cost, last)| Tour { cost, nodes: table.path(full, last) }))
+}
+
+struct HeldKarp {
+    n: usize,
+    // Lightest edge between each pair of nodes, row-major
+    weight: Vec<Option<i32>>,
+    // Cheapest path through the nodes of `mask` ending at `last`, at
+    // mask * n + last; i64::MAX if there is none.
+    cost: Vec<i64>
+}
+
+impl HeldKarp {
+    fn compute<G: WeightedEdges, I: Iterator<Item = usize>>(graph: &G, starts: I) -> Result<HeldKarp, TooManyNodes> {
+        let n = graph.node_count();
+        if n > HELD_KARP_MAX_NODES {
+            return Err(TooManyNodes(n));
+        }
+        let mut weight = vec![None; n * n];
+        let mut neighbors = vec![Vec::new(); n];
+        for u in 0..n {
+            for (v, w) in graph.weighted_adjacent(u) {
+                match weight[u * n + v] {
+                    None => {
+                        weight[u * n + v] = Some(w);
+                        if u != v {
+                            neighbors[u].push(v);
+                        }
+                    }
+                    Some(lightest) if w < lightest => weight[u * n + v] = Some(w),
+                    _ => {}
+                }
+            }
+        }
+
+        let mut cost = vec![i64::MAX; n << n];
+        for start in starts {
+            cost[(1 << start) * n + start] = 0;
+        }
+        // Adding a node makes a larger mask, so masks in increasing order
+        // are final by the time they are extended.
+        for mask in 1..1usize << n {
+            for last in 0..n {
+                let c = cost[mask * n + last];
+                if c == i64::MAX {
+                    continue;
+                }
+                for &next in neighbors[last].iter().filter(|&&v| mask & (1 << v) == 0) {
+                    let extended = (mask | 1 << next) * n + next;
+                    let through = c + weight[last * n + next].unwrap() as i64;
+                    if through < cost[extended] {
+                        cost[extended] = through;
+                    }
+                }
+            }
+        }
+        Ok(HeldKarp { n, weight, cost })
+    }
+
+    fn cost(&self, mask: usize, last: usize) -> Option<i64> {
+        match self.cost[mask * self.n + last] {
+            i64::MAX => None,
+            c => Some(c)
+        }
+    }
+
+    fn weight(&self, u: usize, v: usize) -> Option<i32> {
+        self.weight[u * self.n + v]
+    }
+
+    // Walks back from `last` through predecessors that account for the cost.
+    fn path(&self, mut mask: usize, mut last: usize) -> Vec<usize> {
+        let mut nodes = vec![last];
+        while mask != 1 << last {
+            let c = self.cost(mask, last).unwrap();
+            let rest = mask & !(1 << last);
+            last = (0..self.n)
+                .find(|&u| rest & (1 << u) != 0 && match (self.cost(rest, u), self.weight(u, last)) {
+                    (Some(before), Some(w)) => before + w as i64 == c,
+                    _ => false
+                })
+                .unwrap();
+            mask = rest;
+            nodes.push(last);
+        }
+        nodes.reverse();
+        nodes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{shortest_hamiltonian_cycle, shortest_hamiltonian_path, TooManyNodes, Tour};
+    use test_util::Lcg;
+    use weighted_graph::WeightedGraph;
+
+    // Cost of visiting `nodes` in order, or None if an edge is missing.
+    fn walk_cost(g: &WeightedGraph, nodes: &[usize]) -> Option<i64> {
+        nodes.windows(2)
+            .map(|step| g.neighbors(step[0]).filter(|&(v, _)| v == step[1]).map(|(_, w)| w as i64).min())
+            .sum()
+    }
+
+    // Every permutation of 0..n with the first `fixed` nodes in place.
+    fn permutations(n: usize, fixed: usize) -> Vec<Vec<usize>> {
+        fn extend(prefix: &mut Vec<usize>, n: usize, out: &mut Vec<Vec<usize>>) {
+            if prefix.len() == n {
+                out.push(prefix.clone());
+            }
+            for v in 0..n {
+                if !prefix.contains(&v) {
+                    prefix.push(v);
+                    extend(prefix, n, out);
+                    prefix.pop();
+                }
+            }
+        }
+        let mut out = Vec::new();
+        extend(&mut (0..fixed).collect(), n, &mut out);
+        out
+    }
+
+    #[test]
+    fn square_with_diagonals() {
+        let mut g = WeightedGraph::new(4);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(2, 3, 1);
+        g.add_edge(3, 0, 5);
+        g.add_edge(0, 2, 2);
+        g.add_edge(1, 3, 2);
+        let path = shortest_hamiltonian_path(&g).unwrap().unwrap();
+        assert_eq!(path.cost, 3);
+        assert!(path.nodes == vec![0, 1, 2, 3] || path.nodes == vec![3, 2, 1, 0]);
+        let tour = shortest_hamiltonian_cycle(&g).unwrap().unwrap();
+        assert_eq!(tour.cost, 6);
+        assert_eq!(walk_cost(&g, &[&tour.nodes[..], &[0]].concat()), Some(6));
+    }
+
+    #[test]
+    fn impossible_and_small() {
+        // A star has a Hamiltonian path only with at most two leaves.
+        let mut g = WeightedGraph::new(4);
+        g.add_edge(0, 1, 1);
+        g.add_edge(0, 2, 1);
+        g.add_edge(0, 3, 1);
+        assert_eq!(shortest_hamiltonian_path(&g).unwrap(), None);
+        assert_eq!(shortest_hamiltonian_cycle(&g).unwrap(), None);
+
+        assert_eq!(shortest_hamiltonian_path(&WeightedGraph::new(0)).unwrap(), None);
+        assert_eq!(shortest_hamiltonian_path(&WeightedGraph::new(1)).unwrap(), Some(Tour { cost: 0, nodes: vec![0] }));
+        let mut g = WeightedGraph::new(1);
+        assert_eq!(shortest_hamiltonian_cycle(&g).unwrap(), None);
+        g.add_edge(0, 0, 4);
+        assert_eq!(shortest_hamiltonian_cycle(&g).unwrap(), Some(Tour { cost: 4, nodes: vec![0] }));
+        let mut g = WeightedGraph::new(2);
+        g.add_edge(0, 1, 3);
+        g.add_edge(0, 1, -2);
+        assert_eq!(shortest_hamiltonian_cycle(&g).unwrap(), Some(Tour { cost: -4, nodes: vec![0, 1] }));
+    }
+
+    #[test]
+    fn too_many_nodes() {
+        let g = WeightedGraph::new(21);
+        assert_eq!(shortest_hamiltonian_path(&g), Err(TooManyNodes(21)));
+        assert_eq!(shortest_hamiltonian_cycle(&g), Err(TooManyNodes(21)));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = Lcg(3);
+        for _ in 0..40 {
+            let n = 1 + rng.next(7);
+            let mut g = WeightedGraph::new(n);
+            for _ in 0..rng.next(3 * n) {
+                g.add_edge(rng.next(n), rng.next(n), rng.next(20) as i32 - 5);
+            }
+            let best_path = permutations(n, 0).iter().filter_map(|p| walk_cost(&g, p)).min();
+            let path = shortest_hamiltonian_path(&g).unwrap();
+            assert_eq!(path.as_ref().map(|t| t.cost), best_path);
+            if let Some(t) = path {
+                assert_eq!(walk_cost(&g, &t.nodes), Some(t.cost));
+            }
+
+            if n < 3 {
+                continue;
+            }
+            let best_cycle = permutations(n, 1).iter().filter_map(|p| walk_cost(&g, &[&p[..], &[0]].concat())).min();
+            let cycle = shortest_hamiltonian_cycle(&g).unwrap();
+            assert_eq!(cycle.as_ref().map(|t| t.cost), best_cycle);
+            if let Some(t) = cycle {
+                assert_eq!(t.nodes[0], 0);
+                assert_eq!(walk_cost(&g, &[&t.nodes[..], &[0]].concat()), Some(t.cost));
+            }
+        }
+    }
+}
@@ -82,6 +82,32 @@ impl<T: Ord> BinaryHeap<T> {
     }
 }
 
+#[cfg(feature = "serde")]
+mod serialize {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::de::Error;
+
+    use super::BinaryHeap;
+
+    impl<T: Serialize> Serialize for BinaryHeap<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.array.serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Ord + Deserialize<'de>> Deserialize<'de> for BinaryHeap<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let array = Vec::<T>::deserialize(deserializer)?;
+            for i in 1..array.len() {
+                if array[i] < array[BinaryHeap::<T>::get_parent(i)] {
+                    return Err(D::Error::custom(format!("heap order violated at index {}", i)));
+                }
+            }
+            Ok(BinaryHeap { array })
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::BinaryHeap;
@@ -165,6 +191,25 @@ mod test {
         assert!(h.is_empty());
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip () {
+        use serde_json;
+
+        let mut h = BinaryHeap::new();
+        h.insert(5);
+        h.insert(4);
+        h.insert(6);
+        h.insert(3);
+        let json = serde_json::to_string(&h).unwrap();
+        let mut h: BinaryHeap<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(h.remove_min(), 3);
+        assert_eq!(h.remove_min(), 4);
+
+        let err = serde_json::from_str::<BinaryHeap<i32>>("[1, 3, 2, 0]").err().unwrap();
+        assert!(err.to_string().contains("heap order violated at index 3"));
+    }
+
     #[test]
     fn heapsort_vector () {
         let mut v = vec![3,4,-1,0,192,20];
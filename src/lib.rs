@@ -1,16 +1,52 @@
 #![feature(cell_extras, test)]
 
 extern crate test;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+#[cfg(test)]
+extern crate proptest;
 
+pub mod coloring;
+pub mod connectivity;
+pub mod csr_graph;
+pub mod directed_graph;
+pub mod dot;
+pub mod eulerian;
+pub mod generators;
+pub mod graph;
+pub mod graph_io;
+pub mod hamiltonian;
 pub mod heap;
 pub mod linked_list;
 pub mod linked_list_third;
 pub mod linked_list_fourth;
 pub mod linked_list_fifth;
 pub mod lis;
+pub mod matching;
+pub mod metrics;
+pub mod paths;
+pub mod shortest_paths;
+pub mod tree;
 pub mod undirected_graph;
 pub mod unefficient_queue;
 pub mod weighted_graph;
 
 #[cfg(test)]
 mod benchmark_queue;
+
+#[cfg(test)]
+mod benchmark_shortest_paths;
+
+#[cfg(test)]
+mod benchmark_csr;
+
+#[cfg(test)]
+mod benchmark_traversal;
+
+#[cfg(test)]
+mod property_tests;
+
+#[cfg(test)]
+mod test_util;
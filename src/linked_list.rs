@@ -109,6 +109,30 @@ impl<'a, T: 'a> Iterator for IterMut<'a, T> {
 
 
 
+#[cfg(feature = "serde")]
+mod serialize {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::List;
+
+    // Serialized as a sequence of elements from top to bottom of the stack.
+    impl<T: Serialize> Serialize for List<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter())
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for List<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let mut list = List::new();
+            for t in Vec::<T>::deserialize(deserializer)?.into_iter().rev() {
+                list.push(t);
+            }
+            Ok(list)
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::List;
@@ -197,4 +221,19 @@ mod test {
         } // Mutable references are released here
         assert_eq!(list.peek(), Some(&7));
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        use serde_json;
+
+        let mut l = List::new();
+        l.push(1);
+        l.push(2);
+        l.push(3);
+        let json = serde_json::to_string(&l).unwrap();
+        assert_eq!(json, "[3,2,1]");
+        let l: List<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(l.iter().cloned().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
 }
@@ -114,6 +114,30 @@ impl<'a, T: 'a> Iterator for IterMut<'a, T> {
     }
 }
 
+#[cfg(feature = "serde")]
+mod serialize {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::List;
+
+    // Serialized as a sequence of elements from front to back.
+    impl<T: Serialize> Serialize for List<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter())
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for List<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let mut list = List::new();
+            for t in Vec::<T>::deserialize(deserializer)? {
+                list.push(t);
+            }
+            Ok(list)
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::List;
@@ -192,4 +216,17 @@ mod test {
         assert_eq!(iter.next(), Some(&mut 3));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        use serde_json;
+
+        let mut list = List::new();
+        list.push(1); list.push(2); list.push(3);
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, "[1,2,3]");
+        let list: List<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
 }
@@ -138,6 +138,38 @@ impl<T> DoubleEndedIterator for IntoIter<T> {
 }
 
 
+#[cfg(feature = "serde")]
+mod serialize {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::List;
+
+    // Serialized as a sequence of elements from front to back.
+    impl<T: Serialize> Serialize for List<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::SerializeSeq;
+
+            let mut seq = serializer.serialize_seq(None)?;
+            let mut current = self.head.clone();
+            while let Some(node) = current {
+                seq.serialize_element(&node.borrow().data)?;
+                current = node.borrow().next.clone();
+            }
+            seq.end()
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for List<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let mut list = List::new();
+            for t in Vec::<T>::deserialize(deserializer)? {
+                list.push_back(t);
+            }
+            Ok(list)
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::List;
@@ -226,4 +258,17 @@ mod test {
         assert_eq!(iter.next_back(), None);
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        use serde_json;
+
+        let mut list = List::new();
+        list.push_back(2); list.push_back(3); list.push_front(1);
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, "[1,2,3]");
+        let list: List<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
 }
@@ -57,6 +57,30 @@ impl<'a, T: 'a> Iterator for Iter<'a, T> {
 
 
 
+#[cfg(feature = "serde")]
+mod serialize {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::List;
+
+    // Serialized as a sequence of elements from head to tail.
+    impl<T: Serialize> Serialize for List<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter())
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for List<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let mut list = List::new();
+            for t in Vec::<T>::deserialize(deserializer)?.into_iter().rev() {
+                list = list.append(t);
+            }
+            Ok(list)
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::List;
@@ -93,4 +117,16 @@ mod test {
             i = i - 1;
         }
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        use serde_json;
+
+        let list = List::new().append(1).append(2).append(3);
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, "[3,2,1]");
+        let list: List<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
 }
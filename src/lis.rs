@@ -2,27 +2,18 @@
 /// That is the longest sequence (p_i) s.t. for all i, p_i < p_(i + 1)
 /// and list[p_i] < list[p_(i + 1)]
 pub fn longest_increasing_subsequence<T: Ord>(list: &Vec<T>) -> usize {
-    let mut lis_ending_at_i: Vec<usize> = vec![0; list.len() + 1];
-    let mut curr_i: usize = 1;
-    let mut curr_max: usize = 0;
+    // tails[k] is the smallest last element of an increasing subsequence
+    // of length k + 1 seen so far; it only grows with k.
+    let mut tails: Vec<&T> = Vec::new();
     for x in list {
-        let mut max: usize = 1;
-        for j in 0..curr_i {
-            max = if x > &list[j] && max < lis_ending_at_i[j] {
-                lis_ending_at_i[j]
-            } else {
-                max
-            }
-        };
-        lis_ending_at_i[curr_i] = max + 1;
-        curr_max = if curr_max < max + 1 {
-            max + 1
+        let k = tails.partition_point(|&t| t < x);
+        if k == tails.len() {
+            tails.push(x);
         } else {
-            curr_max
-        };
-        curr_i = curr_i + 1;
+            tails[k] = x;
+        }
     }
-    curr_max
+    tails.len()
 }
 
 #[cfg(test)]
@@ -35,6 +26,13 @@ mod test {
         assert_eq!(5, res);
     }
 
+    #[test]
+    fn single_and_repeated() {
+        assert_eq!(1, longest_increasing_subsequence(&vec!(3)));
+        assert_eq!(1, longest_increasing_subsequence(&vec!(0, 0)));
+        assert_eq!(2, longest_increasing_subsequence(&vec!(1, 1, 2, 2)));
+    }
+
     #[test]
     fn empty_list() {
         let res: usize = longest_increasing_subsequence(&Vec::<i32>::new());
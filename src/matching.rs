@@ -0,0 +1,481 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use graph::{GraphBase, WeightedEdges};
+
+/// A cycle of odd length, which keeps a graph from being bipartite.
+/// Each node has an edge to the next one, and the last to the first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OddCycle {
+    pub nodes: Vec<usize>
+}
+
+impl fmt::Display for OddCycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "odd cycle through nodes {:?}", self.nodes)
+    }
+}
+
+impl ::std::error::Error for OddCycle {}
+
+/// Splits the nodes into two sides with every edge going from one side to
+/// the other, both in increasing order. Each connected component puts its
+/// smallest node on the first side.
+pub fn bipartition<G: GraphBase>(graph: &G) -> Result<(Vec<usize>, Vec<usize>), OddCycle> {
+    const UNSEEN: usize = usize::MAX;
+    let n = graph.node_count();
+    let mut side = vec![UNSEEN; n];
+    let mut parent = vec![UNSEEN; n];
+    let mut queue = VecDeque::new();
+    for root in 0..n {
+        if side[root] != UNSEEN {
+            continue;
+        }
+        side[root] = 0;
+        parent[root] = root;
+        queue.push_back(root);
+        while let Some(u) = queue.pop_front() {
+            for v in graph.adjacent(u) {
+                if side[v] == UNSEEN {
+                    side[v] = 1 - side[u];
+                    parent[v] = u;
+                    queue.push_back(v);
+                } else if side[v] == side[u] {
+                    return Err(OddCycle { nodes: odd_cycle(&parent, u, v) });
+                }
+            }
+        }
+    }
+    Ok((0..n).partition(|&u| side[u] == 0))
+}
+
+// Closes the edge u-v with the BFS tree paths from u and v to their common
+// ancestor. Both ends are at the same depth, so the cycle length is odd.
+fn odd_cycle(parent: &[usize], u: usize, v: usize) -> Vec<usize> {
+    let (mut a, mut b) = (u, v);
+    let mut from_u = vec![u];
+    let mut from_v = Vec::new();
+    while a != b {
+        from_v.push(b);
+        a = parent[a];
+        b = parent[b];
+        from_u.push(a);
+    }
+    from_u.extend(from_v.into_iter().rev());
+    from_u
+}
+
+/// Maximum matching of a bipartite graph (Hopcroft-Karp), in O(E sqrt(V)).
+/// `left[u]` tells which side `u` is on. Returns the mate of every node.
+pub fn hopcroft_karp<G: GraphBase>(graph: &G, left: &[bool]) -> Vec<Option<usize>> {
+    const NONE: usize = usize::MAX;
+    const INFINITY: usize = usize::MAX;
+    let n = graph.node_count();
+    let mut mate = vec![NONE; n];
+    let mut layer = vec![INFINITY; n];
+    let mut queue = VecDeque::new();
+    let mut stack = Vec::new();
+    // Right nodes taken to go from each stack entry to the next
+    let mut via = Vec::new();
+    loop {
+        // Layers of left nodes along shortest alternating paths from free ones
+        let mut found = false;
+        for u in 0..n {
+            layer[u] = INFINITY;
+            if left[u] && mate[u] == NONE {
+                layer[u] = 0;
+                queue.push_back(u);
+            }
+        }
+        while let Some(u) = queue.pop_front() {
+            for v in graph.adjacent(u) {
+                let w = mate[v];
+                if w == NONE {
+                    found = true;
+                } else if layer[w] == INFINITY {
+                    layer[w] = layer[u] + 1;
+                    queue.push_back(w);
+                }
+            }
+        }
+        if !found {
+            break;
+        }
+
+        // Vertex-disjoint augmenting paths along the layers
+        for root in 0..n {
+            if !left[root] || mate[root] != NONE {
+                continue;
+            }
+            stack.push((root, graph.adjacent(root)));
+            while let Some(&mut (u, ref mut adjacent)) = stack.last_mut() {
+                match adjacent.next() {
+                    Some(v) if mate[v] == NONE => {
+                        via.push(v);
+                        for (&(u, _), &v) in stack.iter().zip(via.iter()) {
+                            mate[u] = v;
+                            mate[v] = u;
+                        }
+                        break;
+                    }
+                    Some(v) => {
+                        let w = mate[v];
+                        if layer[w] == layer[u] + 1 {
+                            via.push(v);
+                            stack.push((w, graph.adjacent(w)));
+                        }
+                    }
+                    None => {
+                        layer[u] = INFINITY;
+                        stack.pop();
+                        via.pop();
+                    }
+                }
+            }
+            stack.clear();
+            via.clear();
+        }
+    }
+    mate.iter().map(|&v| if v == NONE { None } else { Some(v) }).collect()
+}
+
+/// Maximum matching of a bipartite graph, as `(u, v)` pairs with `u` on the
+/// first side of `bipartition`, in increasing order of `u`.
+pub fn maximum_matching<G: GraphBase>(graph: &G) -> Result<Vec<(usize, usize)>, OddCycle> {
+    let (first, _) = bipartition(graph)?;
+    let mut left = vec![false; graph.node_count()];
+    for &u in first.iter() {
+        left[u] = true;
+    }
+    let mate = hopcroft_karp(graph, &left);
+    Ok(first.into_iter().filter_map(|u| mate[u].map(|v| (u, v))).collect())
+}
+
+/// Why `min_cost_assignment` refused its workers and jobs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidAssignment {
+    NodeOutOfRange(usize),
+    /// The node is listed twice, as workers, jobs or both.
+    Repeated(usize)
+}
+
+impl fmt::Display for InvalidAssignment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InvalidAssignment::NodeOutOfRange(u) => write!(f, "node {} out of range", u),
+            InvalidAssignment::Repeated(u) => write!(f, "node {} listed twice", u)
+        }
+    }
+}
+
+impl ::std::error::Error for InvalidAssignment {}
+
+/// Assignment of every worker, or every job if there are fewer, to a
+/// distinct partner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment {
+    /// Sum of the weights of the chosen edges.
+    pub cost: i64,
+    /// Chosen edges, as `(worker, job)` in the order of the workers.
+    pub pairs: Vec<(usize, usize)>
+}
+
+/// Minimum cost assignment of `workers` to distinct `jobs` along the edges
+/// of the graph (Hungarian algorithm), in O(n^2 m) for the n nodes on the
+/// smaller side and the m on the other. With more workers than jobs, every
+/// job is given a distinct worker instead. With parallel edges, the
+/// lightest one counts; edges that do not join a worker to a job are ignored.
+/// Returns None when the smaller side cannot be entirely assigned, and an
+/// error when a node is out of range or listed twice.
+/// For a maximum weight assignment, negate the weights.
+pub fn min_cost_assignment<G: WeightedEdges>(graph: &G, workers: &[usize], jobs: &[usize])
+                                             -> Result<Option<Assignment>, InvalidAssignment> {
+    let n = graph.node_count();
+    let mut listed = vec![false; n];
+    for &u in workers.iter().chain(jobs.iter()) {
+        if u >= n {
+            return Err(InvalidAssignment::NodeOutOfRange(u));
+        }
+        if listed[u] {
+            return Err(InvalidAssignment::Repeated(u));
+        }
+        listed[u] = true;
+    }
+    // The Hungarian algorithm needs no more rows than columns.
+    let transposed = workers.len() > jobs.len();
+    let (rows, columns) = if transposed { (jobs, workers) } else { (workers, jobs) };
+    let mut column_of = vec![usize::MAX; n];
+    for (j, &v) in columns.iter().enumerate() {
+        column_of[v] = j;
+    }
+
+    // Missing edges cost more than any assignment made of actual edges.
+    let max_weight = rows.iter()
+        .flat_map(|&u| graph.weighted_adjacent(u))
+        .map(|(_, w)| (w as i64).abs())
+        .max()
+        .unwrap_or(0);
+    let missing = 2 * (rows.len() as i64 + 1) * (max_weight + 1);
+    let mut cost = vec![vec![missing; columns.len()]; rows.len()];
+    for (i, &u) in rows.iter().enumerate() {
+        for (v, w) in graph.weighted_adjacent(u) {
+            if column_of[v] != usize::MAX {
+                let c = &mut cost[i][column_of[v]];
+                *c = (*c).min(w as i64);
+            }
+        }
+    }
+
+    let column = hungarian(&cost, columns.len());
+    if (0..rows.len()).any(|i| cost[i][column[i]] == missing) {
+        return Ok(None);
+    }
+    // (worker, job) positions, sorted by worker
+    let mut pairs: Vec<(usize, usize)> = (0..rows.len())
+        .map(|i| if transposed { (column[i], i) } else { (i, column[i]) })
+        .collect();
+    pairs.sort();
+    Ok(Some(Assignment {
+        cost: (0..rows.len()).map(|i| cost[i][column[i]]).sum(),
+        pairs: pairs.into_iter().map(|(i, j)| (workers[i], jobs[j])).collect()
+    }))
+}
+
+// Column of every row in a minimum cost assignment of an n x m matrix with
+// n <= m, using row and column potentials and shortest augmenting paths.
+fn hungarian(cost: &[Vec<i64>], m: usize) -> Vec<usize> {
+    const NONE: usize = usize::MAX;
+    let n = cost.len();
+    let mut row_potential = vec![0i64; n];
+    // Column m is a virtual column used as the start of each augmenting path
+    let mut column_potential = vec![0i64; m + 1];
+    let mut row_of = vec![NONE; m + 1];
+    let mut previous = vec![m; m + 1];
+    for i in 0..n {
+        row_of[m] = i;
+        let mut j0 = m;
+        let mut min_slack = vec![i64::MAX; m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = row_of[j0];
+            let mut delta = i64::MAX;
+            let mut j1 = m;
+            for j in 0..m {
+                if used[j] {
+                    continue;
+                }
+                let slack = cost[i0][j] - row_potential[i0] - column_potential[j];
+                if slack < min_slack[j] {
+                    min_slack[j] = slack;
+                    previous[j] = j0;
+                }
+                if min_slack[j] < delta {
+                    delta = min_slack[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=m {
+                if used[j] {
+                    row_potential[row_of[j]] += delta;
+                    column_potential[j] -= delta;
+                } else {
+                    min_slack[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if row_of[j0] == NONE {
+                break;
+            }
+        }
+        // Flip the assignments along the augmenting path
+        while j0 != m {
+            let j1 = previous[j0];
+            row_of[j0] = row_of[j1];
+            j0 = j1;
+        }
+    }
+    let mut column = vec![NONE; n];
+    for j in 0..m {
+        if row_of[j] != NONE {
+            column[row_of[j]] = j;
+        }
+    }
+    column
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bipartition, hungarian, maximum_matching, min_cost_assignment, Assignment, InvalidAssignment, OddCycle};
+    use test_util::{graph, Lcg};
+    use weighted_graph::WeightedGraph;
+
+    #[test]
+    fn bipartite() {
+        let s = graph(6, &[(0, 1), (1, 2), (2, 3), (3, 0), (4, 5)]);
+        assert_eq!(bipartition(&s), Ok((vec![0, 2, 4], vec![1, 3, 5])));
+        assert_eq!(bipartition(&graph(0, &[])), Ok((vec![], vec![])));
+    }
+
+    #[test]
+    fn odd_cycles() {
+        let s = graph(6, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (0, 5)]);
+        let cycle = bipartition(&s).unwrap_err().nodes;
+        assert_eq!(cycle.len() % 2, 1);
+        for i in 0..cycle.len() {
+            assert!(s.from(cycle[i]).contains(&cycle[(i + 1) % cycle.len()]));
+        }
+        assert_eq!(bipartition(&graph(2, &[(0, 1), (1, 1)])), Err(OddCycle { nodes: vec![1] }));
+        assert_eq!(OddCycle { nodes: vec![0, 1, 2] }.to_string(), "odd cycle through nodes [0, 1, 2]");
+    }
+
+    #[test]
+    fn matching() {
+        // 0, 1, 2 on the left; 3, 4, 5 on the right. A greedy matching of
+        // 0-3 and 2-4 must be augmented to cover 1.
+        let s = graph(6, &[(0, 3), (0, 4), (1, 3), (2, 4), (2, 5)]);
+        assert_eq!(maximum_matching(&s).unwrap(), vec![(0, 4), (1, 3), (2, 5)]);
+
+        let star = graph(4, &[(0, 1), (0, 2), (0, 3)]);
+        assert_eq!(maximum_matching(&star).unwrap().len(), 1);
+        assert!(maximum_matching(&graph(3, &[(0, 1), (1, 2), (2, 0)])).is_err());
+    }
+
+    fn brute_force_matching(n: usize, edges: &[(usize, usize)]) -> usize {
+        fn search(edges: &[(usize, usize)], used: &mut Vec<bool>) -> usize {
+            match edges.split_first() {
+                None => 0,
+                Some((&(u, v), rest)) => {
+                    let mut best = search(rest, used);
+                    if !used[u] && !used[v] {
+                        used[u] = true;
+                        used[v] = true;
+                        best = best.max(1 + search(rest, used));
+                        used[u] = false;
+                        used[v] = false;
+                    }
+                    best
+                }
+            }
+        }
+        search(edges, &mut vec![false; n])
+    }
+
+    #[test]
+    fn random_matchings() {
+        let mut rng = Lcg(7);
+        for _ in 0..50 {
+            let (a, b) = (1 + rng.next(5), 1 + rng.next(5));
+            let edges: Vec<(usize, usize)> = (0..rng.next(12)).map(|_| (rng.next(a), a + rng.next(b))).collect();
+            let s = graph(a + b, &edges);
+            let matching = maximum_matching(&s).unwrap();
+            assert_eq!(matching.len(), brute_force_matching(a + b, &edges));
+            let mut used = vec![false; a + b];
+            for &(u, v) in matching.iter() {
+                assert!(s.from(u).contains(&v));
+                assert!(!used[u] && !used[v]);
+                used[u] = true;
+                used[v] = true;
+            }
+        }
+    }
+
+    #[test]
+    fn hungarian_matrix() {
+        let cost = vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]];
+        assert_eq!(hungarian(&cost, 3), vec![1, 0, 2]);
+        let cost = vec![vec![7, 3, 9, 1], vec![2, 8, 4, 1]];
+        assert_eq!(hungarian(&cost, 4), vec![3, 0]);
+    }
+
+    #[test]
+    fn assignment() {
+        // Workers 0, 1, 2 and jobs 3, 4, 5
+        let mut g = WeightedGraph::new(6);
+        for &(u, v, w) in [(0, 3, 4), (0, 4, 1), (0, 5, 3), (1, 3, 2), (1, 4, 0), (1, 5, 5), (2, 3, 3), (2, 4, 2), (2, 5, 2)].iter() {
+            g.add_edge(u, v, w);
+        }
+        let (workers, jobs) = ([0, 1, 2], [3, 4, 5]);
+        assert_eq!(min_cost_assignment(&g, &workers, &jobs),
+                   Ok(Some(Assignment { cost: 5, pairs: vec![(0, 4), (1, 3), (2, 5)] })));
+        g.add_edge(0, 5, -10);
+        assert_eq!(min_cost_assignment(&g, &workers, &jobs).unwrap().unwrap().cost, -7);
+        assert_eq!(min_cost_assignment(&g, &[2], &jobs).unwrap().unwrap().pairs, vec![(2, 4)]);
+        assert_eq!(min_cost_assignment(&g, &[], &jobs), Ok(Some(Assignment { cost: 0, pairs: vec![] })));
+        // More workers than jobs: the job goes to the cheapest worker.
+        assert_eq!(min_cost_assignment(&g, &workers, &[5]), Ok(Some(Assignment { cost: -10, pairs: vec![(0, 5)] })));
+        assert_eq!(min_cost_assignment(&g, &[2, 1, 0], &[5, 4]), Ok(Some(Assignment { cost: -10, pairs: vec![(1, 4), (0, 5)] })));
+    }
+
+    #[test]
+    fn invalid_assignment() {
+        let g = WeightedGraph::new(4);
+        assert_eq!(min_cost_assignment(&g, &[0, 1], &[1, 2]), Err(InvalidAssignment::Repeated(1)));
+        assert_eq!(min_cost_assignment(&g, &[0, 0], &[2, 3]), Err(InvalidAssignment::Repeated(0)));
+        assert_eq!(min_cost_assignment(&g, &[0], &[4]), Err(InvalidAssignment::NodeOutOfRange(4)));
+    }
+
+    #[test]
+    fn incomplete_assignment() {
+        // Both workers can only do job 2; the edge between workers is ignored.
+        let mut g = WeightedGraph::new(4);
+        g.add_edge(0, 2, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(0, 1, 1);
+        assert_eq!(min_cost_assignment(&g, &[0, 1], &[2, 3]), Ok(None));
+        assert_eq!(min_cost_assignment(&g, &[2, 3], &[0, 1]), Ok(None));
+        g.add_edge(1, 3, 5);
+        assert_eq!(min_cost_assignment(&g, &[0, 1], &[2, 3]).unwrap().unwrap().pairs, vec![(0, 2), (1, 3)]);
+    }
+
+    fn brute_force_assignment(cost: &[Vec<Option<i64>>], n_jobs: usize) -> Option<i64> {
+        fn search(cost: &[Vec<Option<i64>>], used: &mut Vec<bool>) -> Option<i64> {
+            let (row, rest) = match cost.split_first() {
+                Some(split) => split,
+                None => return Some(0)
+            };
+            let mut best: Option<i64> = None;
+            for j in 0..used.len() {
+                if let (false, Some(c)) = (used[j], row[j]) {
+                    used[j] = true;
+                    if let Some(total) = search(rest, used) {
+                        best = Some(best.map_or(c + total, |b| b.min(c + total)));
+                    }
+                    used[j] = false;
+                }
+            }
+            best
+        }
+        search(cost, &mut vec![false; n_jobs])
+    }
+
+    #[test]
+    fn random_assignments() {
+        let mut rng = Lcg(11);
+        for _ in 0..50 {
+            let a = 1 + rng.next(4);
+            let b = a + rng.next(3);
+            let mut g = WeightedGraph::new(a + b);
+            let mut cost = vec![vec![None; b]; a];
+            for (u, row) in cost.iter_mut().enumerate() {
+                for (v, c) in row.iter_mut().enumerate() {
+                    if rng.next(3) > 0 {
+                        let w = rng.next(21) as i32 - 10;
+                        g.add_edge(u, a + v, w);
+                        *c = Some(w as i64);
+                    }
+                }
+            }
+            let workers: Vec<usize> = (0..a).collect();
+            let jobs: Vec<usize> = (a..a + b).collect();
+            let result = min_cost_assignment(&g, &workers, &jobs).unwrap();
+            let swapped = min_cost_assignment(&g, &jobs, &workers).unwrap();
+            assert_eq!(swapped.map(|r| r.cost), result.as_ref().map(|r| r.cost));
+            assert_eq!(result.as_ref().map(|r| r.cost), brute_force_assignment(&cost, b));
+            if let Some(result) = result {
+                let total: i64 = result.pairs.iter().map(|&(u, v)| g.edge_weight(u, v).unwrap() as i64).sum();
+                assert_eq!(total, result.cost);
+            }
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use graph::{GraphBase, WeightedEdges};
+use heap::BinaryHeap;
+use paths;
+use shortest_paths;
+
+// Up to this many nodes, all distances come from a single Floyd-Warshall.
+const FLOYD_MAX_NODES: usize = 128;
+
+// Calls `f` with the distances from every node in turn, i32::MAX for the
+// nodes it cannot reach. Uses Floyd-Warshall on small graphs, then BFS when
+// every weight is 1 and Dijkstra otherwise.
+fn for_each_distance_row<G: WeightedEdges, F: FnMut(usize, &[i32])>(graph: &G, mut f: F) {
+    let n = graph.node_count();
+    if n <= FLOYD_MAX_NODES {
+        let floyd = shortest_paths::floyd(graph);
+        let mut row = vec![0; n];
+        for u in 0..n {
+            for (v, d) in row.iter_mut().enumerate() {
+                // Floyd's diagonal is the shortest closed walk.
+                *d = if u == v { 0 } else { floyd.get_shortest_path(u, v) };
+            }
+            f(u, &row);
+        }
+    } else if (0..n).all(|u| graph.weighted_adjacent(u).all(|(_, w)| w == 1)) {
+        for u in 0..n {
+            f(u, &paths::bfs_distances(graph, u));
+        }
+    } else {
+        for u in 0..n {
+            f(u, &shortest_paths::dijkstra(graph, u));
+        }
+    }
+}
+
+/// Largest distance from each node to any other node, or i32::MAX if some
+/// node cannot be reached. Weights must be non-negative.
+pub fn eccentricity<G: WeightedEdges>(graph: &G) -> Vec<i32> {
+    let mut result = vec![0; graph.node_count()];
+    for_each_distance_row(graph, |u, row| result[u] = row.iter().cloned().max().unwrap_or(0));
+    result
+}
+
+/// Smallest eccentricity, or None for a graph without nodes.
+pub fn radius<G: WeightedEdges>(graph: &G) -> Option<i32> {
+    eccentricity(graph).into_iter().min()
+}
+
+/// Largest eccentricity, i32::MAX if the graph is disconnected, or None for
+/// a graph without nodes.
+pub fn diameter<G: WeightedEdges>(graph: &G) -> Option<i32> {
+    eccentricity(graph).into_iter().max()
+}
+
+/// Closeness centrality: how near each node is to the nodes it can reach,
+/// scaled by the share of nodes it can reach (Wasserman and Faust), so that
+/// nodes of small components do not look central. 0 for isolated nodes.
+pub fn closeness<G: WeightedEdges>(graph: &G) -> Vec<f64> {
+    let n = graph.node_count();
+    let mut result = vec![0.0; n];
+    for_each_distance_row(graph, |u, row| {
+        let reachable: Vec<i64> = row.iter().filter(|&&d| d != i32::MAX).map(|&d| d as i64).collect();
+        let total: i64 = reachable.iter().sum();
+        let others = (reachable.len() - 1) as f64;
+        if total > 0 {
+            result[u] = others / total as f64 * others / (n - 1) as f64;
+        }
+    });
+    result
+}
+
+/// Betweenness centrality (Brandes): for each node, the sum over ordered
+/// pairs of other nodes (s, t) of the share of shortest s-t paths through
+/// it. On undirected graphs, each pair is thus counted in both directions.
+/// Weights must be positive; parallel edges make distinct paths.
+pub fn betweenness<G: WeightedEdges>(graph: &G) -> Vec<f64> {
+    let n = graph.node_count();
+    let mut centrality = vec![0.0; n];
+    let mut dist: Vec<Option<i64>> = vec![None; n];
+    let mut paths = vec![0.0f64; n];
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut dependency = vec![0.0f64; n];
+    let mut settled = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    for s in 0..n {
+        for v in 0..n {
+            dist[v] = None;
+            paths[v] = 0.0;
+            predecessors[v].clear();
+            dependency[v] = 0.0;
+            settled[v] = false;
+        }
+        order.clear();
+
+        // Dijkstra, counting shortest paths and keeping their predecessors
+        dist[s] = Some(0);
+        paths[s] = 1.0;
+        let mut heap = BinaryHeap::new();
+        heap.insert((0i64, s));
+        while !heap.is_empty() {
+            let (d, u) = heap.remove_min();
+            if settled[u] {
+                continue;
+            }
+            settled[u] = true;
+            order.push(u);
+            for (v, weight) in graph.weighted_adjacent(u) {
+                let nd = d + weight as i64;
+                match dist[v] {
+                    Some(best) if nd > best => {}
+                    Some(best) if nd == best => {
+                        paths[v] += paths[u];
+                        predecessors[v].push(u);
+                    }
+                    _ => {
+                        dist[v] = Some(nd);
+                        paths[v] = paths[u];
+                        predecessors[v].clear();
+                        predecessors[v].push(u);
+                        heap.insert((nd, v));
+                    }
+                }
+            }
+        }
+
+        // Dependencies accumulate from the farthest nodes back to s.
+        while let Some(v) = order.pop() {
+            for &u in predecessors[v].iter() {
+                dependency[u] += paths[u] / paths[v] * (1.0 + dependency[v]);
+            }
+            if v != s {
+                centrality[v] += dependency[v];
+            }
+        }
+    }
+    centrality
+}
+
+/// Number of nodes of each degree: `result[d]` nodes have degree d.
+pub fn degree_distribution<G: GraphBase>(graph: &G) -> Vec<usize> {
+    let mut count = Vec::new();
+    for u in 0..graph.node_count() {
+        let d = graph.degree(u);
+        if d >= count.len() {
+            count.resize(d + 1, 0);
+        }
+        count[d] += 1;
+    }
+    count
+}
+
+/// Local clustering coefficient: the share of pairs of neighbors of each
+/// node that are themselves linked. Self-loops and parallel edges are
+/// ignored. 0 for nodes with fewer than two neighbors.
+pub fn clustering<G: GraphBase>(graph: &G) -> Vec<f64> {
+    const NONE: usize = usize::MAX;
+    let n = graph.node_count();
+    // neighbor_of[v] == u when v is a neighbor of the node u being looked at
+    let mut neighbor_of = vec![NONE; n];
+    let mut seen_from = vec![NONE; n];
+    let mut result = vec![0.0; n];
+    for (u, coefficient) in result.iter_mut().enumerate() {
+        let mut neighbors = Vec::new();
+        for v in graph.adjacent(u) {
+            if v != u && neighbor_of[v] != u {
+                neighbor_of[v] = u;
+                neighbors.push(v);
+            }
+        }
+        let k = neighbors.len();
+        if k < 2 {
+            continue;
+        }
+        let mut links = 0;
+        for (i, &v) in neighbors.iter().enumerate() {
+            // Distinct stamp per (u, v), so parallel edges from v count once
+            let stamp = u * n + i;
+            for w in graph.adjacent(v) {
+                if w != v && neighbor_of[w] == u && seen_from[w] != stamp {
+                    seen_from[w] = stamp;
+                    links += 1;
+                }
+            }
+        }
+        // Each link was seen from both of its ends.
+        *coefficient = links as f64 / (k * (k - 1)) as f64;
+    }
+    result
+}
+
+/// Mean of the local clustering coefficients, 0 for a graph without nodes.
+pub fn average_clustering<G: GraphBase>(graph: &G) -> f64 {
+    let coefficients = clustering(graph);
+    if coefficients.is_empty() {
+        0.0
+    } else {
+        coefficients.iter().sum::<f64>() / coefficients.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{average_clustering, betweenness, closeness, clustering, degree_distribution,
+                diameter, eccentricity, radius, FLOYD_MAX_NODES};
+    use generators;
+    use shortest_paths::{floyd, FloydShortestPaths};
+    use test_util::graph;
+    use undirected_graph::UndirectedGraph;
+    use weighted_graph::WeightedGraph;
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).abs() < 1e-9, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn distances_on_a_path() {
+        let s = graph(5, &[(0, 1), (1, 2), (2, 3), (3, 4)]);
+        assert_eq!(eccentricity(&s), vec![4, 3, 2, 3, 4]);
+        assert_eq!(radius(&s), Some(2));
+        assert_eq!(diameter(&s), Some(4));
+        assert_close(&closeness(&s), &[0.4, 4.0 / 7.0, 4.0 / 6.0, 4.0 / 7.0, 0.4]);
+        // Ordered pairs: node 1 is on the paths between 0 and 2, 3, 4.
+        assert_close(&betweenness(&s), &[0.0, 6.0, 8.0, 6.0, 0.0]);
+    }
+
+    #[test]
+    fn disconnected_and_empty() {
+        let s = graph(4, &[(0, 1), (1, 2)]);
+        assert_eq!(eccentricity(&s), vec![i32::MAX; 4]);
+        assert_eq!(diameter(&s), Some(i32::MAX));
+        assert_eq!(closeness(&s)[3], 0.0);
+        // Node 0 reaches 2 of the 3 other nodes at a total distance of 3.
+        assert!((closeness(&s)[0] - 2.0 / 3.0 * 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(radius(&graph(0, &[])), None);
+        assert_eq!(diameter(&graph(1, &[])), Some(0));
+    }
+
+    #[test]
+    fn weighted_distances() {
+        // The direct edge 0-2 is longer than going through 1.
+        let mut g = WeightedGraph::new(3);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(0, 2, 5);
+        assert_eq!(eccentricity(&g), vec![2, 1, 2]);
+        assert_close(&betweenness(&g), &[0.0, 2.0, 0.0]);
+        g.set_weight(0, 2, 2);
+        // Now two shortest paths from 0 to 2, one through 1.
+        assert_close(&betweenness(&g), &[0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn large_graphs_match_floyd() {
+        // Past FLOYD_MAX_NODES, rows come from BFS or Dijkstra.
+        let n = FLOYD_MAX_NODES + 50;
+        let list = generators::erdos_renyi(n, 0.03, 5);
+        let s: UndirectedGraph<()> = list.to_undirected();
+        let mut weighted = list.clone();
+        generators::random_weights(&mut weighted, 1..20, 5);
+        let g = weighted.to_weighted();
+        let check = |ecc: Vec<i32>, floyd: FloydShortestPaths| {
+            for (u, &e) in ecc.iter().enumerate() {
+                let max = (0..n).map(|v| if u == v { 0 } else { floyd.get_shortest_path(u, v) }).max().unwrap();
+                assert_eq!(e, max);
+            }
+        };
+        check(eccentricity(&s), floyd(&s));
+        check(eccentricity(&g), floyd(&g));
+    }
+
+    #[test]
+    fn star_betweenness() {
+        let s: UndirectedGraph<()> = generators::star(5).to_undirected();
+        // The center is on the path between each of the 4 * 3 ordered pairs of leaves.
+        assert_close(&betweenness(&s), &[12.0, 0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(degree_distribution(&s), vec![0, 4, 0, 0, 1]);
+        assert_eq!(radius(&s), Some(1));
+        assert_eq!(diameter(&s), Some(2));
+    }
+
+    #[test]
+    fn clustering_coefficients() {
+        // A triangle 0-1-2 with a pendant 3 on node 2, parallel edge and self-loop on 1.
+        let s = graph(4, &[(0, 1), (1, 2), (2, 0), (2, 3), (1, 0), (1, 1)]);
+        assert_close(&clustering(&s), &[1.0, 1.0, 1.0 / 3.0, 0.0]);
+        assert!((average_clustering(&s) - (7.0 / 3.0) / 4.0).abs() < 1e-9);
+        let complete: UndirectedGraph<()> = generators::complete(6).to_undirected();
+        assert_close(&clustering(&complete), &[1.0; 6]);
+        assert_eq!(average_clustering(&graph(0, &[])), 0.0);
+    }
+}
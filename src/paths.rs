@@ -0,0 +1,76 @@
+use std::collections::VecDeque;
+
+use graph::{Bfs, GraphBase};
+
+/// Whether `v` can be reached from `u`.
+pub fn has_path<G: GraphBase>(graph: &G, u: usize, v: usize) -> bool {
+    Bfs::new(graph, u).any(|w| w == v)
+}
+
+/// Number of edges on a shortest path from `source` to every node, or
+/// i32::MAX for the nodes it cannot reach.
+pub fn bfs_distances<G: GraphBase>(graph: &G, source: usize) -> Vec<i32> {
+    let mut dist = vec![i32::MAX; graph.node_count()];
+    let mut queue = VecDeque::new();
+    dist[source] = 0;
+    queue.push_back(source);
+    while let Some(u) = queue.pop_front() {
+        for v in graph.adjacent(u) {
+            if dist[v] == i32::MAX {
+                dist[v] = dist[u] + 1;
+                queue.push_back(v);
+            }
+        }
+    }
+    dist
+}
+
+/// Path from `u` to `v` with the fewest edges, as the list of its nodes
+/// from `u` to `v`, or None if `v` cannot be reached.
+pub fn shortest_unweighted_path<G: GraphBase>(graph: &G, u: usize, v: usize) -> Option<Vec<usize>> {
+    const NO_PARENT: usize = usize::MAX;
+    let mut parent = vec![NO_PARENT; graph.node_count()];
+    let mut queue = VecDeque::new();
+    parent[u] = u;
+    queue.push_back(u);
+    while let Some(w) = queue.pop_front() {
+        if w == v {
+            let mut path = vec![v];
+            let mut x = v;
+            while x != u {
+                x = parent[x];
+                path.push(x);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for x in graph.adjacent(w) {
+            if parent[x] == NO_PARENT {
+                parent[x] = w;
+                queue.push_back(x);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bfs_distances, has_path, shortest_unweighted_path};
+    use directed_graph::DirectedGraph;
+
+    #[test]
+    fn directed_paths() {
+        let mut g = DirectedGraph::new(4);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(0, 2);
+        assert!(has_path(&g, 0, 2));
+        assert!(!has_path(&g, 2, 0));
+        assert!(!has_path(&g, 0, 3));
+        assert_eq!(shortest_unweighted_path(&g, 0, 2), Some(vec![0, 2]));
+        assert_eq!(shortest_unweighted_path(&g, 1, 1), Some(vec![1]));
+        assert_eq!(shortest_unweighted_path(&g, 2, 0), None);
+        assert_eq!(bfs_distances(&g, 0), vec![0, 1, 1, i32::MAX]);
+    }
+}
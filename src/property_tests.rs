@@ -0,0 +1,205 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap as StdHeap, VecDeque};
+
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use super::heap::{self, BinaryHeap};
+use super::lis::longest_increasing_subsequence;
+use super::{linked_list, linked_list_fifth, linked_list_fourth, linked_list_third, unefficient_queue};
+use super::shortest_paths;
+use super::graph_io::EdgeList;
+
+#[derive(Debug, Clone)]
+enum Op {
+    PushFront(i32),
+    PushBack(i32),
+    PopFront,
+    PopBack,
+    Peek
+}
+
+// Operations for stacks and queues, which only push at one end.
+fn one_ended_ops() -> impl Strategy<Value = Vec<Op>> {
+    vec(prop_oneof![
+        any::<i32>().prop_map(Op::PushBack),
+        Just(Op::PopFront),
+        Just(Op::Peek)
+    ], 0..100)
+}
+
+fn deque_ops() -> impl Strategy<Value = Vec<Op>> {
+    vec(prop_oneof![
+        any::<i32>().prop_map(Op::PushFront),
+        any::<i32>().prop_map(Op::PushBack),
+        Just(Op::PopFront),
+        Just(Op::PopBack),
+        Just(Op::Peek)
+    ], 0..100)
+}
+
+// Undirected edges with non-negative weights over 1 to 12 nodes.
+fn graph() -> impl Strategy<Value = EdgeList> {
+    (1usize..12).prop_flat_map(|n| vec((0..n, 0..n, 0i32..100), 0..40).prop_map(move |edges| {
+        EdgeList { n_nodes: n, edges }
+    }))
+}
+
+fn brute_force_lis(list: &[i32]) -> usize {
+    (0u32..1 << list.len())
+        .filter_map(|subset| {
+            let chosen: Vec<i32> = (0..list.len()).filter(|&i| subset & 1 << i != 0).map(|i| list[i]).collect();
+            if chosen.windows(2).all(|w| w[0] < w[1]) { Some(chosen.len()) } else { None }
+        })
+        .max()
+        .unwrap()
+}
+
+proptest! {
+    #[test]
+    fn stack_matches_vec_deque(ops in one_ended_ops()) {
+        let mut list = linked_list::List::new();
+        let mut model = VecDeque::new();
+        for op in ops {
+            match op {
+                Op::PushBack(x) => {
+                    list.push(x);
+                    model.push_front(x);
+                }
+                Op::PopFront => prop_assert_eq!(list.pop(), model.pop_front()),
+                _ => prop_assert_eq!(list.peek(), model.front())
+            }
+        }
+        prop_assert_eq!(list.into_iter().collect::<Vec<_>>(), model.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn persistent_stack_matches_vec_deque(ops in one_ended_ops()) {
+        let mut list = linked_list_third::List::new();
+        let mut model = VecDeque::new();
+        for op in ops {
+            match op {
+                Op::PushBack(x) => {
+                    list = list.append(x);
+                    model.push_front(x);
+                }
+                Op::PopFront => {
+                    list = list.tail();
+                    model.pop_front();
+                }
+                _ => prop_assert_eq!(list.head(), model.front())
+            }
+        }
+        prop_assert_eq!(list.iter().collect::<Vec<_>>(), model.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn deque_matches_vec_deque(ops in deque_ops()) {
+        let mut list = linked_list_fourth::List::new();
+        let mut model = VecDeque::new();
+        for op in ops {
+            match op {
+                Op::PushFront(x) => {
+                    list.push_front(x);
+                    model.push_front(x);
+                }
+                Op::PushBack(x) => {
+                    list.push_back(x);
+                    model.push_back(x);
+                }
+                Op::PopFront => prop_assert_eq!(list.pop_front(), model.pop_front()),
+                Op::PopBack => prop_assert_eq!(list.pop_back(), model.pop_back()),
+                Op::Peek => {
+                    prop_assert_eq!(list.peek_front().map(|x| *x), model.front().cloned());
+                    prop_assert_eq!(list.peek_back().map(|x| *x), model.back().cloned());
+                }
+            }
+        }
+        prop_assert_eq!(list.into_iter().rev().collect::<Vec<_>>(), model.into_iter().rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn queue_matches_vec_deque(ops in one_ended_ops()) {
+        let mut list = linked_list_fifth::List::new();
+        let mut model = VecDeque::new();
+        for op in ops {
+            match op {
+                Op::PushBack(x) => {
+                    list.push(x);
+                    model.push_back(x);
+                }
+                Op::PopFront => prop_assert_eq!(list.pop(), model.pop_front()),
+                _ => prop_assert_eq!(list.peek(), model.front())
+            }
+        }
+        prop_assert_eq!(list.into_iter().collect::<Vec<_>>(), model.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn unefficient_queue_matches_vec_deque(ops in one_ended_ops()) {
+        let mut queue = unefficient_queue::Queue::new();
+        let mut model = VecDeque::new();
+        for op in ops {
+            match op {
+                Op::PushBack(x) => {
+                    queue.enqueue(x);
+                    model.push_back(x);
+                }
+                Op::PopFront => prop_assert_eq!(queue.dequeue(), model.pop_front()),
+                _ => prop_assert_eq!(queue.peek(), model.front())
+            }
+        }
+        prop_assert_eq!(queue.into_iter().collect::<Vec<_>>(), model.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn heap_matches_std(ops in one_ended_ops()) {
+        let mut heap = BinaryHeap::new();
+        let mut model = StdHeap::new();
+        for op in ops {
+            match op {
+                Op::PushBack(x) => {
+                    heap.insert(x);
+                    model.push(Reverse(x));
+                }
+                Op::PopFront if !model.is_empty() => prop_assert_eq!(heap.remove_min(), model.pop().unwrap().0),
+                _ => {
+                    prop_assert_eq!(heap.is_empty(), model.is_empty());
+                    if let Some(&Reverse(min)) = model.peek() {
+                        prop_assert_eq!(*heap.get_min(), min);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn heapsort_matches_sort(mut v in vec(any::<i32>(), 0..200)) {
+        let mut expected = v.clone();
+        expected.sort();
+        heap::heapsort(&mut v);
+        prop_assert_eq!(v, expected);
+    }
+
+    #[test]
+    fn floyd_matches_dijkstra(list in graph()) {
+        let g = list.to_weighted();
+        let floyd = shortest_paths::floyd(&g);
+        for u in 0..g.size() {
+            let dijkstra = shortest_paths::dijkstra(&g, u);
+            for (v, &d) in dijkstra.iter().enumerate() {
+                // Floyd's diagonal is the shortest closed walk, not 0.
+                if u != v {
+                    prop_assert_eq!(floyd.get_shortest_path(u, v), d);
+                }
+            }
+        }
+        prop_assert_eq!(shortest_paths::johnson(&g).unwrap().get_shortest_path(0, g.size() - 1),
+                        floyd.get_shortest_path(0, g.size() - 1));
+    }
+
+    #[test]
+    fn lis_matches_brute_force(list in vec(-5i32..5, 0..12)) {
+        prop_assert_eq!(longest_increasing_subsequence(&list), brute_force_lis(&list));
+    }
+}
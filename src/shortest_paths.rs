@@ -0,0 +1,309 @@
+use std::thread;
+
+use graph::WeightedEdges;
+use heap::BinaryHeap;
+
+/// All-pairs shortest path lengths. Unreachable pairs have length i32::MAX.
+/// The diagonal holds the shortest closed walk through each node.
+#[cfg_attr(feature = "serde", derive(::serde::Serialize))]
+pub struct FloydShortestPaths {
+    n: usize,
+    // Row-major n x n matrix
+    w: Vec<i32>
+}
+
+/// Floyd-Warshall all-pairs shortest paths, in O(n^3).
+pub fn floyd<G: WeightedEdges>(graph: &G) -> FloydShortestPaths {
+    FloydShortestPaths::compute(graph)
+}
+
+/// Computes the same matrix as `floyd` with a cache-friendly blocked
+/// Floyd-Warshall spread over `n_threads` threads.
+pub fn floyd_parallel<G: WeightedEdges>(graph: &G, n_threads: usize) -> FloydShortestPaths {
+    FloydShortestPaths::compute_parallel(graph, n_threads)
+}
+
+/// All-pairs shortest paths with Johnson's algorithm, in O(n * m * log(n)).
+/// Returns None if the graph contains a negative cycle.
+pub fn johnson<G: WeightedEdges>(graph: &G) -> Option<FloydShortestPaths> {
+    FloydShortestPaths::compute_johnson(graph)
+}
+
+/// All-pairs shortest paths, using Johnson's algorithm on sparse graphs
+/// and Floyd-Warshall on dense ones. Returns None if the graph contains a
+/// negative cycle.
+pub fn all_pairs_shortest_paths<G: WeightedEdges>(graph: &G) -> Option<FloydShortestPaths> {
+    if is_sparse(graph) {
+        return johnson(graph);
+    }
+    // Floyd would overflow going around a negative cycle: check first, in
+    // O(n * m), which a dense graph can afford next to O(n^3).
+    bellman_ford_potential(graph)?;
+    Some(floyd(graph))
+}
+
+/// Single source shortest path lengths from `source` (Dijkstra).
+/// All weights must be non-negative. Unreachable nodes get i32::MAX.
+pub fn dijkstra<G: WeightedEdges>(graph: &G, source: usize) -> Vec<i32> {
+    assert!(source < graph.node_count());
+    let potential = vec![0; graph.node_count()];
+    dijkstra_with_potential(graph, source, &potential)
+        .into_iter()
+        .map(|d| d.map_or(i32::MAX, |d| d as i32))
+        .collect()
+}
+
+// Johnson runs in O(n * m * log(n)) against O(n^3) for Floyd.
+fn is_sparse<G: WeightedEdges>(graph: &G) -> bool {
+    let n = graph.node_count();
+    let n_edges: usize = (0..n).map(|u| graph.degree(u)).sum();
+    let log_n = (usize::BITS - n.leading_zeros()) as usize;
+    n_edges * log_n < n * n
+}
+
+// Bellman-Ford from a virtual source linked to every node with weight 0.
+// Returns None if a negative cycle is reachable.
+fn bellman_ford_potential<G: WeightedEdges>(graph: &G) -> Option<Vec<i64>> {
+    let n = graph.node_count();
+    let mut h = vec![0i64; n];
+    for _ in 0..n {
+        let mut changed = false;
+        for u in 0..n {
+            for (v, weight) in graph.weighted_adjacent(u) {
+                if h[u] + (weight as i64) < h[v] {
+                    h[v] = h[u] + weight as i64;
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            return Some(h);
+        }
+    }
+    None
+}
+
+// Dijkstra on the graph reweighted with w'(u, v) = w(u, v) + h(u) - h(v),
+// which must be non-negative. Returns the original (not reweighted) distances.
+fn dijkstra_with_potential<G: WeightedEdges>(graph: &G, source: usize, h: &[i64]) -> Vec<Option<i64>> {
+    let mut dist: Vec<Option<i64>> = vec![None; graph.node_count()];
+    let mut heap = BinaryHeap::new();
+    dist[source] = Some(0);
+    heap.insert((0i64, source));
+    while !heap.is_empty() {
+        let (d, u) = heap.remove_min();
+        if dist[u].is_some_and(|best| d > best) {
+            continue;
+        }
+        for (v, weight) in graph.weighted_adjacent(u) {
+            let nd = d + weight as i64 + h[u] - h[v];
+            if dist[v].is_none_or(|best| nd < best) {
+                dist[v] = Some(nd);
+                heap.insert((nd, v));
+            }
+        }
+    }
+    dist.iter()
+        .enumerate()
+        .map(|(v, d)| d.map(|d| d - h[source] + h[v]))
+        .collect()
+}
+
+impl FloydShortestPaths {
+    // Adjacency matrix of the graph, keeping the lightest of parallel edges.
+    fn init<G: WeightedEdges>(graph: &G) -> FloydShortestPaths {
+        let n = graph.node_count();
+        let mut sp = FloydShortestPaths {
+            n,
+            // Initialize a N x N matrix with maxint
+            w: vec![i32::MAX; n * n]
+        };
+        for u in 0..n {
+            for (v, weight) in graph.weighted_adjacent(u) {
+                if weight < sp.w[u * n + v] {
+                    sp.w[u * n + v] = weight;
+                }
+            }
+        }
+        sp
+    }
+
+    fn compute<G: WeightedEdges>(graph: &G) -> FloydShortestPaths {
+        let mut sp = FloydShortestPaths::init(graph);
+        let n = sp.n;
+        for k in 0..n {
+            for u in 0..n {
+                for v in 0..n {
+                    if sp.w[u * n + k] == i32::MAX || sp.w[k * n + v] == i32::MAX {
+                        continue;
+                    }
+                    if sp.w[u * n + v] > sp.w[u * n + k] + sp.w[k * n + v] {
+                        sp.w[u * n + v] = sp.w[u * n + k] + sp.w[k * n + v];
+                    }
+
+                }
+            }
+        }
+        sp
+    }
+
+    // Blocked Floyd-Warshall. The matrix is cut in stripes of FLOYD_BLOCK rows.
+    // For each block of pivots k, the pivot stripe is relaxed first, then the
+    // other stripes only read it and are relaxed in parallel.
+    fn compute_parallel<G: WeightedEdges>(graph: &G, n_threads: usize) -> FloydShortestPaths {
+        assert!(n_threads > 0);
+        let mut sp = FloydShortestPaths::init(graph);
+        let n = sp.n;
+        let stripe_len = FLOYD_BLOCK * n;
+        for block_start in (0..n).step_by(FLOYD_BLOCK) {
+            let pivots = block_start..n.min(block_start + FLOYD_BLOCK);
+            let (before, rest) = sp.w.split_at_mut(block_start * n);
+            let (pivot_stripe, after) = rest.split_at_mut(stripe_len.min(rest.len()));
+
+            let mut pivot_row = vec![0; n];
+            for k in pivots.clone() {
+                pivot_row.copy_from_slice(&pivot_stripe[(k - block_start) * n..(k - block_start + 1) * n]);
+                relax_rows(pivot_stripe, n, k, &pivot_row);
+            }
+
+            let pivot_stripe = &*pivot_stripe;
+            let mut stripes: Vec<&mut [i32]> = before.chunks_mut(stripe_len)
+                .chain(after.chunks_mut(stripe_len))
+                .collect();
+            if stripes.is_empty() {
+                continue;
+            }
+            let per_thread = stripes.len().div_ceil(n_threads);
+            thread::scope(|scope| {
+                for group in stripes.chunks_mut(per_thread) {
+                    let pivots = pivots.clone();
+                    scope.spawn(move || {
+                        for stripe in group.iter_mut() {
+                            for k in pivots.clone() {
+                                let row = &pivot_stripe[(k - block_start) * n..(k - block_start + 1) * n];
+                                relax_rows(stripe, n, k, row);
+                            }
+                        }
+                    });
+                }
+            });
+        }
+        sp
+    }
+
+    fn compute_johnson<G: WeightedEdges>(graph: &G) -> Option<FloydShortestPaths> {
+        let h = bellman_ford_potential(graph)?;
+        let n = graph.node_count();
+        let mut sp = FloydShortestPaths {
+            n,
+            w: Vec::with_capacity(n * n)
+        };
+        for u in 0..n {
+            let row = dijkstra_with_potential(graph, u, &h)
+                .into_iter()
+                .map(|d| d.map_or(i32::MAX, |d| d as i32));
+            sp.w.extend(row);
+        }
+        // Keep Floyd's convention: the diagonal is only set by a path through an edge.
+        for u in 0..n {
+            sp.w[u * n + u] = graph.weighted_adjacent(u)
+                .filter_map(|(v, weight)| match sp.w[v * n + u] {
+                    i32::MAX => None,
+                    d => Some(weight + d)
+                })
+                .min()
+                .unwrap_or(i32::MAX);
+        }
+        Some(sp)
+    }
+
+    pub fn get_shortest_path(&self, u: usize, v: usize) -> i32 {
+        self.w[u * self.n + v]
+    }
+
+    /// Updates the shortest paths after an edge u-v of weight `weight` has
+    /// been added to the graph, or an existing one made cheaper, in O(n^2).
+    /// Increasing or removing an edge requires a full recompute.
+    pub fn update_edge(&mut self, u: usize, v: usize, weight: i32) {
+        let n = self.n;
+        assert!(u < n);
+        assert!(v < n);
+        // Distances with at least zero edges, taken before any update.
+        let dist = |sp: &FloydShortestPaths, a: usize, b: usize| -> Option<i64> {
+            match sp.get_shortest_path(a, b) {
+                _ if a == b => Some(0),
+                i32::MAX => None,
+                d => Some(d as i64)
+            }
+        };
+        let to_u: Vec<Option<i64>> = (0..n).map(|a| dist(self, a, u)).collect();
+        let to_v: Vec<Option<i64>> = (0..n).map(|a| dist(self, a, v)).collect();
+        let from_u: Vec<Option<i64>> = (0..n).map(|b| dist(self, u, b)).collect();
+        let from_v: Vec<Option<i64>> = (0..n).map(|b| dist(self, v, b)).collect();
+        let weight = weight as i64;
+        for a in 0..n {
+            for b in 0..n {
+                let via_uv = to_u[a].and_then(|x| from_v[b].map(|y| x + weight + y));
+                let via_vu = to_v[a].and_then(|x| from_u[b].map(|y| x + weight + y));
+                let best = match (via_uv, via_vu) {
+                    (Some(x), Some(y)) => x.min(y),
+                    (x, y) => match x.or(y) {
+                        Some(d) => d,
+                        None => continue
+                    }
+                };
+                if best < self.w[a * n + b] as i64 {
+                    self.w[a * n + b] = best as i32;
+                }
+            }
+        }
+        // The diagonal holds the shortest closed walk, which may cross the edge twice.
+        for &a in [u, v].iter() {
+            if 2 * weight < self.w[a * n + a] as i64 {
+                self.w[a * n + a] = (2 * weight) as i32;
+            }
+        }
+    }
+}
+
+const FLOYD_BLOCK: usize = 64;
+
+// Relaxes every row of `rows` (row-major, `n` columns) through pivot `k`,
+// whose own row is `pivot_row`.
+fn relax_rows(rows: &mut [i32], n: usize, k: usize, pivot_row: &[i32]) {
+    for row in rows.chunks_mut(n) {
+        let to_k = row[k];
+        if to_k == i32::MAX {
+            continue;
+        }
+        for (d, &from_k) in row.iter_mut().zip(pivot_row.iter()) {
+            if from_k != i32::MAX && *d > to_k + from_k {
+                *d = to_k + from_k;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serialize {
+    use serde::{Deserialize, Deserializer};
+    use serde::de::Error;
+
+    use super::FloydShortestPaths;
+
+    #[derive(Deserialize)]
+    struct FloydRepr {
+        n: usize,
+        w: Vec<i32>
+    }
+
+    impl<'de> Deserialize<'de> for FloydShortestPaths {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let repr = FloydRepr::deserialize(deserializer)?;
+            if repr.n.checked_mul(repr.n) != Some(repr.w.len()) {
+                return Err(D::Error::custom(format!("expected {} x {} matrix, found {} values", repr.n, repr.n, repr.w.len())));
+            }
+            Ok(FloydShortestPaths { n: repr.n, w: repr.w })
+        }
+    }
+}
@@ -0,0 +1,56 @@
+// Fixtures shared by the unit tests.
+
+use directed_graph::DirectedGraph;
+use undirected_graph::UndirectedGraph;
+use weighted_graph::WeightedGraph;
+
+// Linear congruential generator for random test cases: reproducible and
+// free of dependencies. Random graphs with guarantees come from `generators`.
+pub struct Lcg(pub u64);
+
+impl Lcg {
+    pub fn next(&mut self, bound: usize) -> usize {
+        self.0 = self.0.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        (self.0 >> 33) as usize % bound
+    }
+}
+
+pub fn graph(n: usize, edges: &[(usize, usize)]) -> UndirectedGraph<()> {
+    let mut s = UndirectedGraph::new(n);
+    for &(u, v) in edges.iter() {
+        s.add_edge(u, v);
+    }
+    s
+}
+
+pub fn digraph(n: usize, edges: &[(usize, usize)]) -> DirectedGraph {
+    let mut g = DirectedGraph::new(n);
+    for &(u, v) in edges.iter() {
+        g.add_edge(u, v);
+    }
+    g
+}
+
+// Connected weighted graph on 10 nodes, without self-loops or parallel edges.
+pub fn sample_graph() -> WeightedGraph {
+    let mut g = WeightedGraph::new(10);
+
+    g.add_edge(0, 1, 4);
+    g.add_edge(0, 5, 1);
+    g.add_edge(1, 2, 4);
+    g.add_edge(1, 6, 2);
+    g.add_edge(1, 8, 4);
+    g.add_edge(2, 3, 5);
+    g.add_edge(2, 6, 1);
+    g.add_edge(2, 8, 1);
+    g.add_edge(3, 4, 1);
+    g.add_edge(3, 9, 10);
+    g.add_edge(4, 5, 3);
+    g.add_edge(4, 6, 5);
+    g.add_edge(4, 7, 1);
+    g.add_edge(4, 9, 2);
+    g.add_edge(5, 6, 1);
+    g.add_edge(5, 7, 5);
+    g.add_edge(7, 9, 2);
+    g
+}
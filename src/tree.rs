@@ -0,0 +1,430 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use graph::GraphBase;
+
+/// Why an undirected graph is not a tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotATree {
+    /// A cycle, as the list of its nodes: each node has an edge to the
+    /// next one, and the last to the first.
+    Cycle(Vec<usize>),
+    /// A node that cannot be reached from the root.
+    Unreachable(usize),
+    /// The root is out of range, or one of the nodes that need not be
+    /// reached.
+    InvalidRoot(usize)
+}
+
+impl fmt::Display for NotATree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NotATree::Cycle(ref nodes) => write!(f, "cycle through nodes {:?}", nodes),
+            NotATree::Unreachable(u) => write!(f, "node {} cannot be reached from the root", u),
+            NotATree::InvalidRoot(u) => write!(f, "node {} cannot be the root", u)
+        }
+    }
+}
+
+impl ::std::error::Error for NotATree {}
+
+/// Tree of an undirected graph hung from a root node, with the parent and
+/// depth of every node.
+#[derive(Debug, Clone)]
+pub struct RootedTree {
+    root: usize,
+    parent: Vec<Option<usize>>,
+    depth: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    // Nodes of the tree in breadth-first order from the root
+    order: Vec<usize>
+}
+
+impl RootedTree {
+    /// Roots the tree at `root`, or returns why the graph is not a tree.
+    pub fn new<G: GraphBase>(graph: &G, root: usize) -> Result<RootedTree, NotATree> {
+        RootedTree::spanning(graph, root, |_| true)
+    }
+
+    /// Same as `new`, but only the nodes for which `required` holds must be
+    /// reached: the others may be left out of the tree. The root must be
+    /// one of the required nodes.
+    pub fn spanning<G: GraphBase, F: Fn(usize) -> bool>(graph: &G, root: usize, required: F) -> Result<RootedTree, NotATree> {
+        let n = graph.node_count();
+        if root >= n || !required(root) {
+            return Err(NotATree::InvalidRoot(root));
+        }
+        let mut tree = RootedTree {
+            root,
+            parent: vec![None; n],
+            depth: vec![None; n],
+            children: vec![Vec::new(); n],
+            order: Vec::new()
+        };
+        tree.depth[root] = Some(0);
+        let mut queue = VecDeque::new();
+        queue.push_back(root);
+        while let Some(u) = queue.pop_front() {
+            tree.order.push(u);
+            let mut parent_edge = tree.parent[u];
+            for v in graph.adjacent(u) {
+                if parent_edge == Some(v) {
+                    // The edge back to the parent, once; a parallel one is a cycle.
+                    parent_edge = None;
+                } else if tree.depth[v].is_some() {
+                    return Err(NotATree::Cycle(tree.cycle_through(u, v)));
+                } else {
+                    tree.parent[v] = Some(u);
+                    tree.depth[v] = tree.depth[u].map(|d| d + 1);
+                    tree.children[u].push(v);
+                    queue.push_back(v);
+                }
+            }
+        }
+        match (0..n).find(|&u| tree.depth[u].is_none() && required(u)) {
+            Some(u) => Err(NotATree::Unreachable(u)),
+            None => Ok(tree)
+        }
+    }
+
+    // Tree paths from u and v up to their common ancestor, closed by the
+    // edge v-u that is not part of the tree.
+    fn cycle_through(&self, u: usize, v: usize) -> Vec<usize> {
+        let (mut a, mut b) = (u, v);
+        let mut from_u = Vec::new();
+        let mut from_v = Vec::new();
+        while a != b {
+            if self.depth[a] >= self.depth[b] {
+                from_u.push(a);
+                a = self.parent[a].unwrap();
+            } else {
+                from_v.push(b);
+                b = self.parent[b].unwrap();
+            }
+        }
+        from_u.push(a);
+        from_u.extend(from_v.into_iter().rev());
+        from_u
+    }
+
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// Whether `u` is part of the tree.
+    pub fn contains(&self, u: usize) -> bool {
+        self.depth[u].is_some()
+    }
+
+    /// Parent of `u`, None for the root and for nodes outside the tree.
+    pub fn parent(&self, u: usize) -> Option<usize> {
+        self.parent[u]
+    }
+
+    /// Number of edges from the root to `u`, None outside the tree.
+    pub fn depth(&self, u: usize) -> Option<usize> {
+        self.depth[u]
+    }
+
+    pub fn parents(&self) -> &[Option<usize>] {
+        &self.parent
+    }
+
+    pub fn depths(&self) -> &[Option<usize>] {
+        &self.depth
+    }
+
+    /// Children of `u`, in the order of its edges.
+    pub fn children(&self, u: usize) -> &[usize] {
+        &self.children[u]
+    }
+
+    /// Number of nodes in the subtree of every node, itself included;
+    /// 0 outside the tree.
+    pub fn subtree_sizes(&self) -> Vec<usize> {
+        let mut size = vec![0; self.parent.len()];
+        for &u in self.order.iter().rev() {
+            size[u] += 1;
+            if let Some(p) = self.parent[u] {
+                size[p] += size[u];
+            }
+        }
+        size
+    }
+
+    /// Longest path between two nodes of the tree, as the list of its
+    /// nodes: found from the deepest node, then the farthest node from it.
+    pub fn diameter_path(&self) -> Vec<usize> {
+        let deepest = *self.order.last().unwrap();
+        // Breadth-first from `deepest`, along child and parent links
+        let mut previous = vec![None; self.parent.len()];
+        let mut seen = vec![false; self.parent.len()];
+        let mut queue = VecDeque::new();
+        let mut farthest = deepest;
+        seen[deepest] = true;
+        queue.push_back(deepest);
+        while let Some(u) = queue.pop_front() {
+            farthest = u;
+            for &v in self.children[u].iter().chain(self.parent[u].iter()) {
+                if !seen[v] {
+                    seen[v] = true;
+                    previous[v] = Some(u);
+                    queue.push_back(v);
+                }
+            }
+        }
+        let mut path = vec![farthest];
+        while let Some(u) = previous[*path.last().unwrap()] {
+            path.push(u);
+        }
+        path
+    }
+
+    /// Number of edges on the longest path between two nodes of the tree.
+    pub fn diameter(&self) -> usize {
+        self.diameter_path().len() - 1
+    }
+
+    /// Ancestor table answering lowest common ancestor queries in
+    /// O(log n), built in O(n log n).
+    pub fn binary_lifting(&self) -> BinaryLifting {
+        BinaryLifting::new(self)
+    }
+
+    /// Euler tour with a sparse table answering lowest common ancestor
+    /// queries in O(1), built in O(n log n).
+    pub fn euler_tour(&self) -> EulerTour {
+        EulerTour::new(self)
+    }
+}
+
+/// Ancestors of every node at each power of two above it.
+pub struct BinaryLifting {
+    // up[k][u] is the 2^k-th ancestor of u, the root if there is none
+    up: Vec<Vec<usize>>,
+    depth: Vec<Option<usize>>
+}
+
+impl BinaryLifting {
+    fn new(tree: &RootedTree) -> BinaryLifting {
+        let n = tree.parent.len();
+        let max_depth = tree.order.last().and_then(|&u| tree.depth[u]).unwrap_or(0);
+        let levels = (usize::BITS - max_depth.leading_zeros()).max(1) as usize;
+        let mut up = Vec::with_capacity(levels);
+        up.push((0..n).map(|u| tree.parent[u].unwrap_or(tree.root)).collect::<Vec<_>>());
+        for k in 1..levels {
+            let next = up[k - 1].iter().map(|&a| up[k - 1][a]).collect();
+            up.push(next);
+        }
+        BinaryLifting { up, depth: tree.depth.clone() }
+    }
+
+    /// Ancestor `k` edges above `u`, or None if `u` is less deep than `k`.
+    pub fn kth_ancestor(&self, mut u: usize, k: usize) -> Option<usize> {
+        if k > self.depth[u].expect("node outside the tree") {
+            return None;
+        }
+        for (level, up) in self.up.iter().enumerate() {
+            if k & (1 << level) != 0 {
+                u = up[u];
+            }
+        }
+        Some(u)
+    }
+
+    /// Deepest node that is an ancestor of both `u` and `v`, counting each
+    /// node as its own ancestor.
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let du = self.depth[u].expect("node outside the tree");
+        let dv = self.depth[v].expect("node outside the tree");
+        let (mut u, mut v) = if du >= dv { (u, v) } else { (v, u) };
+        u = self.kth_ancestor(u, du.max(dv) - du.min(dv)).unwrap();
+        if u == v {
+            return u;
+        }
+        for up in self.up.iter().rev() {
+            if up[u] != up[v] {
+                u = up[u];
+                v = up[v];
+            }
+        }
+        self.up[0][u]
+    }
+
+    /// Number of edges on the path between `u` and `v`.
+    pub fn distance(&self, u: usize, v: usize) -> usize {
+        let a = self.lca(u, v);
+        self.depth[u].unwrap() + self.depth[v].unwrap() - 2 * self.depth[a].unwrap()
+    }
+}
+
+/// Nodes in the order a depth-first walk of the tree goes through them,
+/// with a sparse table of the least deep node over every range.
+pub struct EulerTour {
+    tour: Vec<usize>,
+    // Index in tour of the first visit of every node
+    first: Vec<usize>,
+    // sparse[k][i] is the least deep node of tour[i..i + 2^k]
+    sparse: Vec<Vec<usize>>,
+    depth: Vec<Option<usize>>
+}
+
+impl EulerTour {
+    fn new(tree: &RootedTree) -> EulerTour {
+        let n = tree.parent.len();
+        let mut tour = Vec::with_capacity(2 * tree.order.len());
+        let mut first = vec![usize::MAX; n];
+        // (node, index of the next child to visit)
+        let mut stack = vec![(tree.root, 0)];
+        first[tree.root] = 0;
+        tour.push(tree.root);
+        while let Some(&mut (u, ref mut next)) = stack.last_mut() {
+            match tree.children[u].get(*next) {
+                Some(&v) => {
+                    *next += 1;
+                    first[v] = tour.len();
+                    tour.push(v);
+                    stack.push((v, 0));
+                }
+                None => {
+                    stack.pop();
+                    if let Some(&(p, _)) = stack.last() {
+                        tour.push(p);
+                    }
+                }
+            }
+        }
+        let depth = tree.depth.clone();
+        let shallower = |a: usize, b: usize| if depth[a] <= depth[b] { a } else { b };
+        let mut sparse = vec![tour.clone()];
+        let mut width = 1;
+        while 2 * width <= tour.len() {
+            let previous = sparse.last().unwrap();
+            let next = (0..tour.len() + 1 - 2 * width)
+                .map(|i| shallower(previous[i], previous[i + width]))
+                .collect();
+            sparse.push(next);
+            width *= 2;
+        }
+        EulerTour { tour, first, sparse, depth }
+    }
+
+    /// Nodes of the walk: every node when it is first reached and again
+    /// after each of its children, 2n - 1 entries in all.
+    pub fn tour(&self) -> &[usize] {
+        &self.tour
+    }
+
+    /// Deepest node that is an ancestor of both `u` and `v`, counting each
+    /// node as its own ancestor.
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        assert!(self.depth[u].is_some() && self.depth[v].is_some(), "node outside the tree");
+        let (i, j) = (self.first[u].min(self.first[v]), self.first[u].max(self.first[v]) + 1);
+        let level = (usize::BITS - 1 - (j - i).leading_zeros()) as usize;
+        let (a, b) = (self.sparse[level][i], self.sparse[level][j - (1 << level)]);
+        if self.depth[a] <= self.depth[b] { a } else { b }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NotATree, RootedTree};
+    use generators;
+    use test_util::graph;
+    use undirected_graph::UndirectedGraph;
+
+    //       0
+    //     / | \
+    //    1  2  3
+    //   / \     \
+    //  4   5     6
+    //            |
+    //            7
+    fn sample_tree() -> UndirectedGraph<()> {
+        graph(8, &[(0, 1), (0, 2), (0, 3), (1, 4), (1, 5), (3, 6), (6, 7)])
+    }
+
+    #[test]
+    fn parents_depths_and_sizes() {
+        let tree = RootedTree::new(&sample_tree(), 0).unwrap();
+        assert_eq!(tree.parents(), &[None, Some(0), Some(0), Some(0), Some(1), Some(1), Some(3), Some(6)]);
+        assert_eq!(tree.depths(), &[Some(0), Some(1), Some(1), Some(1), Some(2), Some(2), Some(2), Some(3)]);
+        assert_eq!(tree.children(1), &[4, 5]);
+        assert_eq!(tree.subtree_sizes(), vec![8, 3, 1, 3, 1, 1, 2, 1]);
+
+        let tree = RootedTree::new(&sample_tree(), 6).unwrap();
+        assert_eq!(tree.parent(3), Some(6));
+        assert_eq!(tree.depth(4), Some(4));
+        assert_eq!(tree.subtree_sizes()[3], 6);
+    }
+
+    #[test]
+    fn diameter() {
+        let tree = RootedTree::new(&sample_tree(), 2).unwrap();
+        assert_eq!(tree.diameter(), 5);
+        let path = tree.diameter_path();
+        assert_eq!(path.len(), 6);
+        assert!(path.first() == Some(&7) || path.last() == Some(&7));
+        assert_eq!(RootedTree::new(&graph(1, &[]), 0).unwrap().diameter(), 0);
+    }
+
+    #[test]
+    fn lowest_common_ancestors() {
+        let tree = RootedTree::new(&sample_tree(), 0).unwrap();
+        let lifting = tree.binary_lifting();
+        let euler = tree.euler_tour();
+        assert_eq!(euler.tour(), &[0, 1, 4, 1, 5, 1, 0, 2, 0, 3, 6, 7, 6, 3, 0]);
+        for &(u, v, a) in [(4, 5, 1), (4, 7, 0), (7, 3, 3), (2, 2, 2), (5, 1, 1)].iter() {
+            assert_eq!(lifting.lca(u, v), a);
+            assert_eq!(euler.lca(u, v), a);
+        }
+        assert_eq!(lifting.kth_ancestor(7, 2), Some(3));
+        assert_eq!(lifting.kth_ancestor(7, 4), None);
+        assert_eq!(lifting.distance(4, 7), 5);
+    }
+
+    #[test]
+    fn random_trees_match_naive_lca() {
+        for seed in 0..10 {
+            let n = 1 + 37 * seed as usize;
+            let s: UndirectedGraph<()> = generators::random_tree(n, seed).to_undirected();
+            let tree = RootedTree::new(&s, n / 2).unwrap();
+            let lifting = tree.binary_lifting();
+            let euler = tree.euler_tour();
+            assert_eq!(euler.tour().len(), 2 * n - 1);
+            let ancestors = |mut u: usize| {
+                let mut path = vec![u];
+                while let Some(p) = tree.parent(u) {
+                    path.push(p);
+                    u = p;
+                }
+                path
+            };
+            let end = tree.diameter_path()[0];
+            for u in (0..n).step_by(7) {
+                for v in (0..n).step_by(5) {
+                    let from_v = ancestors(v);
+                    let naive = *ancestors(u).iter().find(|a| from_v.contains(a)).unwrap();
+                    assert_eq!(lifting.lca(u, v), naive);
+                    assert_eq!(euler.lca(u, v), naive);
+                }
+            }
+            let farthest = (0..n).map(|u| lifting.distance(u, end)).max().unwrap();
+            assert_eq!(tree.diameter(), farthest);
+        }
+    }
+
+    #[test]
+    fn not_a_tree() {
+        let s = graph(4, &[(0, 1), (1, 2), (2, 3), (3, 1)]);
+        assert_eq!(RootedTree::new(&s, 0).unwrap_err(), NotATree::Cycle(vec![2, 1, 3]));
+        let s = graph(3, &[(0, 1), (1, 0), (1, 2)]);
+        assert_eq!(RootedTree::new(&s, 2).unwrap_err(), NotATree::Cycle(vec![1, 0]));
+        let s = graph(3, &[(0, 1), (2, 2)]);
+        assert_eq!(RootedTree::new(&s, 2).unwrap_err(), NotATree::Cycle(vec![2]));
+        assert_eq!(RootedTree::new(&s, 0).unwrap_err(), NotATree::Unreachable(2));
+        assert!(RootedTree::spanning(&graph(3, &[(0, 1)]), 1, |u| u != 2).is_ok());
+        assert_eq!(RootedTree::new(&s, 3).unwrap_err(), NotATree::InvalidRoot(3));
+        assert_eq!(RootedTree::spanning(&s, 2, |u| u != 2).unwrap_err(), NotATree::InvalidRoot(2));
+    }
+}
@@ -1,135 +1,218 @@
-pub struct UndirectedGraph<T> {
+use std::fmt::Display;
+use std::iter::{Cloned, Map};
+use std::mem;
+use std::slice::Iter;
+
+use coloring::{self, ColoringOrder};
+use connectivity;
+use csr_graph::CsrGraph;
+use dot;
+use eulerian::{self, NotEulerian};
+use graph::{self, Bfs, BfsTree, Dfs, DfsEvent, DfsTree, GraphBase, GraphError, WeightedEdges};
+use graph_io::EdgeList;
+use matching::{self, OddCycle};
+use metrics;
+use paths;
+use tree::{NotATree, RootedTree};
+
+/// Undirected graph with optional data of type `T` on every node, and data
+/// of type `E` on every edge.
+/// Removing a node leaves its index vacant rather than renumbering the
+/// others; vacant indices are reused by `add_node`.
+/// Algorithms only see the nodes that were not removed: removed nodes never
+/// appear in node lists, and vectors with a value per node index give them
+/// a placeholder, noted on each method. `freeze` and `EdgeList`, which have
+/// no vacant indices, keep them as isolated nodes.
+pub struct UndirectedGraph<T, E = ()> {
     edges: Vec<Vec<usize>>,
-    data: Vec<Option<T>>
+    // Index in edge_list of each entry of edges
+    edge_ids: Vec<Vec<usize>>,
+    edge_list: Vec<(usize, usize, E)>,
+    // Index of each edge of edge_list in the lists of its first and second
+    // node, so that moving an edge in edge_list is O(1)
+    edge_positions: Vec<[usize; 2]>,
+    data: Vec<Option<T>>,
+    // Bumped every time the node at that index is removed
+    generations: Vec<u32>,
+    removed: Vec<bool>,
+    free: Vec<usize>
 }
 
-pub struct DfsIterator<'a, T: 'a> {
-    graph: &'a UndirectedGraph<T>,
-    stack: Vec<usize>,
-    visited: Vec<bool>
+/// Handle on a node that can tell whether the node was removed since,
+/// even if its index was reused by another node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeHandle {
+    index: usize,
+    generation: u32
 }
 
-pub struct BfsIterator<'a, T: 'a> {
-    graph: &'a UndirectedGraph<T>,
-    queue: Vec<usize>,
-    visited: Vec<bool>
+/// Post-order depth-first traversal yielding node data; stops at the
+/// first node without data.
+pub struct DfsIterator<'a, T: 'a, E: 'a = ()> {
+    graph: &'a UndirectedGraph<T, E>,
+    dfs: Dfs<'a, UndirectedGraph<T, E>>
 }
 
-impl<'a, T: Clone> BfsIterator<'a, T> {
-    pub fn new(graph: &'a UndirectedGraph<T>, start: usize) -> BfsIterator<'a, T> {
-        let size = graph.size();
-        let mut it = BfsIterator {
-            graph: graph,
-            queue: vec![start],
-            visited: Vec::<bool>::with_capacity(size)
-        };
-        for i in 0..size {
-            it.visited.push(i == start);
+/// Breadth-first traversal yielding node data; stops at the first node
+/// without data.
+pub struct BfsIterator<'a, T: 'a, E: 'a = ()> {
+    graph: &'a UndirectedGraph<T, E>,
+    bfs: Bfs<'a, UndirectedGraph<T, E>>
+}
+
+impl<'a, T: Clone, E> BfsIterator<'a, T, E> {
+    pub fn new(graph: &'a UndirectedGraph<T, E>, start: usize) -> BfsIterator<'a, T, E> {
+        BfsIterator {
+            graph,
+            bfs: Bfs::new(graph, start)
         }
-        it
     }
 }
 
-impl<'a, T: Clone> Iterator for BfsIterator<'a, T> {
+impl<'a, T: Clone, E> Iterator for BfsIterator<'a, T, E> {
     type Item = T;
 
     fn next(&mut self) -> Option<T> {
-        if self.queue.is_empty() {
-            None
-        } else {
-            let u = self.queue.remove(0);
-            for v in self.graph.from(u) {
-                if !self.visited[*v] {
-                    self.visited[*v] = true;
-                    self.queue.push(*v);
-                }
-            }
-            match self.graph.get_data(u) {
-                Some(t) => Some(t.clone()),
-                _ => None
-            }
-        }
+        let u = self.bfs.next()?;
+        self.graph.get_data(u).cloned()
     }
 }
 
 
-impl<'a, T: Clone> DfsIterator<'a, T> {
-    pub fn new(graph: &'a UndirectedGraph<T>, start: usize) -> DfsIterator<'a, T> {
-        let size = graph.size();
-        let mut it = DfsIterator {
-            graph: graph,
-            stack: vec![start],
-            visited: Vec::with_capacity(size)
-        };
-        for i in 0..size {
-            it.visited.push(i == start);
+impl<'a, T: Clone, E> DfsIterator<'a, T, E> {
+    pub fn new(graph: &'a UndirectedGraph<T, E>, start: usize) -> DfsIterator<'a, T, E> {
+        DfsIterator {
+            graph,
+            dfs: Dfs::new(graph, start)
         }
-        it
     }
 }
 
-impl<'a, T: Clone> Iterator for DfsIterator<'a, T> {
+impl<'a, T: Clone, E> Iterator for DfsIterator<'a, T, E> {
     type Item = T;
-    
+
     fn next(&mut self) -> Option<T> {
-        if self.stack.is_empty() {
-            None
-        } else {
-            let mut next_node = self.stack[self.stack.len() - 1];
-            let mut found = true;
-            
-            while found {
-                found = false;
-                for v in self.graph.from(next_node) {
-                    if !self.visited[*v] {
-                        found = true;
-                        next_node = *v;
-                        self.visited[*v] = true;
-                        self.stack.push(*v);
-                        break;
-                    }
-                }
+        let u = self.dfs.next()?;
+        self.graph.get_data(u).cloned()
+    }
+}
+
+impl<T, E> UndirectedGraph<T, E> {
+    pub fn new(size: usize) -> UndirectedGraph<T, E> {
+        let mut s = UndirectedGraph {
+            edges: Vec::with_capacity(size),
+            edge_ids: Vec::with_capacity(size),
+            edge_list: Vec::new(),
+            edge_positions: Vec::new(),
+            data: Vec::with_capacity(size),
+            generations: vec![0; size],
+            removed: vec![false; size],
+            free: Vec::new()
+        };
+        for _ in 0..size {
+            s.edges.push(Vec::new());
+            s.edge_ids.push(Vec::new());
+            s.data.push(None);
+        }
+        s
+    }
+
+    /// Adds an isolated node and returns its index, reusing the index of a
+    /// removed node if there is one.
+    pub fn add_node(&mut self, t: T) -> usize {
+        match self.free.pop() {
+            Some(u) => {
+                self.removed[u] = false;
+                self.data[u] = Some(t);
+                u
             }
-            self.stack.pop();
-            match self.graph.get_data(next_node) {
-                Some(t) => Some(t.clone()),
-                _ => None
+            None => {
+                self.edges.push(Vec::new());
+                self.edge_ids.push(Vec::new());
+                self.data.push(Some(t));
+                self.generations.push(0);
+                self.removed.push(false);
+                self.data.len() - 1
             }
         }
     }
-}
 
-impl<T> UndirectedGraph<T> {
-    pub fn size(&self) -> usize {
-        self.data.len()
+    /// Removes `u` and its edges, and returns its data. Other nodes keep
+    /// their index, and `u` stays vacant until `add_node` reuses it.
+    pub fn remove_node(&mut self, u: usize) -> Option<T> {
+        assert!(self.contains_node(u));
+        let mut neighbors = mem::take(&mut self.edges[u]);
+        let ids = mem::take(&mut self.edge_ids[u]);
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        for &w in neighbors.iter().filter(|&&w| w != u) {
+            self.retain_edges(w, |x| x != u);
+        }
+        self.remove_edge_ids(ids);
+        self.removed[u] = true;
+        // Only a handle 2^32 removals old could be mistaken for a new one.
+        self.generations[u] = self.generations[u].wrapping_add(1);
+        self.free.push(u);
+        self.data[u].take()
     }
 
-    pub fn add_edge(&mut self, u: usize, v: usize) {
-        self.edges[u].push(v);
-        self.edges[v].push(u);
+    /// Whether `u` is the index of a node that was not removed.
+    pub fn contains_node(&self, u: usize) -> bool {
+        u < self.size() && !self.removed[u]
     }
-    
-    pub fn from(&self, u: usize) -> &Vec<usize> {
-        &self.edges[u]
+
+    /// Number of nodes, not counting removed ones.
+    pub fn live_node_count(&self) -> usize {
+        self.size() - self.free.len()
     }
-}
 
+    /// Indices of the nodes that were not removed, in increasing order.
+    pub fn live_nodes(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.size()).filter(move |&u| !self.removed[u])
+    }
 
-impl<T: Clone> UndirectedGraph<T> {
-    pub fn new(size: usize) -> UndirectedGraph<T> {
-        let mut s = UndirectedGraph {
-            edges: Vec::with_capacity(size),
-            data: Vec::with_capacity(size)
-        };
-        for _ in 0..size {
-            s.edges.push(Vec::new());
-            s.data.push(None);
+    /// Stable handle on `u`.
+    pub fn handle(&self, u: usize) -> NodeHandle {
+        assert!(self.contains_node(u));
+        NodeHandle { index: u, generation: self.generations[u] }
+    }
+
+    /// Index of the node behind `handle`, or None if it was removed.
+    pub fn resolve(&self, handle: NodeHandle) -> Option<usize> {
+        if self.contains_node(handle.index) && self.generations[handle.index] == handle.generation {
+            Some(handle.index)
+        } else {
+            None
         }
-        s
     }
 
+    /// Data of the node behind `handle`, or None if it was removed or has
+    /// no data.
+    pub fn get_data_by_handle(&self, handle: NodeHandle) -> Option<&T> {
+        self.get_data(self.resolve(handle)?)
+    }
+
+    /// Removes the node behind `handle` like `remove_node`, or returns an
+    /// error if it was already removed.
+    pub fn remove_node_by_handle(&mut self, handle: NodeHandle) -> Result<Option<T>, GraphError> {
+        match self.resolve(handle) {
+            Some(u) => Ok(self.remove_node(u)),
+            None => Err(GraphError::RemovedNode(handle.index))
+        }
+    }
+
+    /// Adds an edge carrying `e` between the nodes behind two handles, or
+    /// returns an error if either was removed.
+    pub fn add_edge_by_handles(&mut self, a: NodeHandle, b: NodeHandle, e: E) -> Result<(), GraphError> {
+        let u = self.resolve(a).ok_or(GraphError::RemovedNode(a.index))?;
+        let v = self.resolve(b).ok_or(GraphError::RemovedNode(b.index))?;
+        self.add_edge_with(u, v, e);
+        Ok(())
+    }
+
+    /// Sets the data of `u`. Does nothing if `u` is not a node.
     pub fn set_data(&mut self, u: usize, t: T) {
-        if u < self.data.len() {
+        if self.contains_node(u) {
             self.data[u] = Some(t);
         }
     }
@@ -143,18 +226,559 @@ impl<T: Clone> UndirectedGraph<T> {
 
     }
 
-    pub fn dfs(&mut self, start: usize) -> DfsIterator<T> {
+    /// Number of node indices, including removed ones: every index is
+    /// in `0..size()`.
+    pub fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Adds an edge carrying `e`. Parallel edges and self-loops are allowed.
+    pub fn add_edge_with(&mut self, u: usize, v: usize, e: E) {
+        assert!(!self.removed[u] && !self.removed[v]);
+        let id = self.edge_list.len();
+        self.edge_list.push((u, v, e));
+        self.edges[u].push(v);
+        self.edge_ids[u].push(id);
+        self.edges[v].push(u);
+        self.edge_ids[v].push(id);
+        // Same list for a self-loop: its entries are the last two.
+        self.edge_positions.push([self.edges[u].len() - 1 - (u == v) as usize, self.edges[v].len() - 1]);
+    }
+
+    /// Sets the data of `u`, or returns an error if it does not exist.
+    pub fn try_set_data(&mut self, u: usize, t: T) -> Result<(), GraphError> {
+        self.check_node(u)?;
+        self.data[u] = Some(t);
+        Ok(())
+    }
+
+    fn check_node(&self, u: usize) -> Result<(), GraphError> {
+        GraphError::check_node(u, self.size())?;
+        if self.removed[u] {
+            return Err(GraphError::RemovedNode(u));
+        }
+        Ok(())
+    }
+
+    /// Removes every edge between `u` and `v`.
+    /// Returns false if there was no such edge.
+    pub fn remove_edge(&mut self, u: usize, v: usize) -> bool {
+        let ids: Vec<usize> = self.edges[u].iter()
+            .zip(self.edge_ids[u].iter())
+            .filter(|&(&x, _)| x == v)
+            .map(|(_, &id)| id)
+            .collect();
+        if ids.is_empty() {
+            return false;
+        }
+        self.retain_edges(u, |x| x != v);
+        self.retain_edges(v, |x| x != u);
+        self.remove_edge_ids(ids);
+        true
+    }
+
+    // Keeps the adjacency entries of `u` whose neighbor passes `keep`, in
+    // a single pass over both lists.
+    fn retain_edges<F: Fn(usize) -> bool>(&mut self, u: usize, keep: F) {
+        let (edges, ids) = (&mut self.edges[u], &mut self.edge_ids[u]);
+        let mut kept = 0;
+        for i in 0..edges.len() {
+            if keep(edges[i]) {
+                let id = ids[i];
+                let positions = &mut self.edge_positions[id];
+                // A self-loop has both of its entries in this list.
+                let end = if self.edge_list[id].0 == u && positions[0] == i { 0 } else { 1 };
+                positions[end] = kept;
+                edges[kept] = edges[i];
+                ids[kept] = id;
+                kept += 1;
+            }
+        }
+        edges.truncate(kept);
+        ids.truncate(kept);
+    }
+
+    // Drops edges already detached from both ends. Each slot is filled by
+    // the last edge, whose id is fixed at its ends in O(1); going from the
+    // highest id down, the moved edge is never one still to be removed.
+    fn remove_edge_ids(&mut self, mut ids: Vec<usize>) {
+        ids.sort_unstable_by(|a, b| b.cmp(a));
+        // A self-loop is listed twice at its node.
+        ids.dedup();
+        for id in ids {
+            self.edge_list.swap_remove(id);
+            self.edge_positions.swap_remove(id);
+            if id == self.edge_list.len() {
+                continue;
+            }
+            let (x, y, _) = self.edge_list[id];
+            let [p, q] = self.edge_positions[id];
+            self.edge_ids[x][p] = id;
+            self.edge_ids[y][q] = id;
+        }
+    }
+
+    /// Data of an edge between `u` and `v`, the first one added if there
+    /// are parallel edges.
+    pub fn edge_data(&self, u: usize, v: usize) -> Option<&E> {
+        let i = self.edges[u].iter().position(|&x| x == v)?;
+        Some(&self.edge_list[self.edge_ids[u][i]].2)
+    }
+
+    /// Mutable data of an edge between `u` and `v`, the first one added if
+    /// there are parallel edges.
+    pub fn edge_data_mut(&mut self, u: usize, v: usize) -> Option<&mut E> {
+        let i = self.edges[u].iter().position(|&x| x == v)?;
+        Some(&mut self.edge_list[self.edge_ids[u][i]].2)
+    }
+
+    /// Every edge once, as `(u, v, data)` with `u` and `v` in the order
+    /// they were given when adding the edge. Edges come in the order they
+    /// were added until one is removed: the last edge then takes its place.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, &E)> + '_ {
+        self.edge_list.iter().map(|&(u, v, ref e)| (u, v, e))
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edge_list.len()
+    }
+
+    pub fn has_edge(&self, u: usize, v: usize) -> bool {
+        self.edges[u].contains(&v)
+    }
+
+    /// Number of edges at `u`, a self-loop counting twice.
+    pub fn degree(&self, u: usize) -> usize {
+        self.edges[u].len()
+    }
+
+    pub fn from(&self, u: usize) -> &Vec<usize> {
+        &self.edges[u]
+    }
+
+    /// Component id of every node, numbered from 0 in the order of the
+    /// smallest node of each component. Removed nodes get usize::MAX.
+    pub fn connected_components(&self) -> Vec<usize> {
+        self.per_live_node(connectivity::connected_components, usize::MAX)
+    }
+
+    /// Edges whose removal disconnects their component, as `(u, v)` with `u < v`.
+    pub fn bridges(&self) -> Vec<(usize, usize)> {
+        connectivity::biconnectivity(self).bridges
+    }
+
+    /// Nodes whose removal disconnects their component, in increasing order.
+    pub fn articulation_points(&self) -> Vec<usize> {
+        connectivity::biconnectivity(self).articulation_points
+    }
+
+    /// Edges of each biconnected component. Self-loops belong to none.
+    pub fn biconnected_components(&self) -> Vec<Vec<(usize, usize)>> {
+        connectivity::biconnectivity(self).components
+    }
+
+    /// Whether `v` can be reached from `u`. False if either was removed.
+    pub fn has_path(&self, u: usize, v: usize) -> bool {
+        self.contains_node(u) && self.contains_node(v) && paths::has_path(self, u, v)
+    }
+
+    /// Path from `u` to `v` with the fewest edges, as the list of its nodes
+    /// from `u` to `v`, or None if `v` cannot be reached.
+    pub fn shortest_unweighted_path(&self, u: usize, v: usize) -> Option<Vec<usize>> {
+        if !self.contains_node(u) || !self.contains_node(v) {
+            return None;
+        }
+        paths::shortest_unweighted_path(self, u, v)
+    }
+
+    /// Nodes of a cycle in order, if the graph has one.
+    /// Self-loops and parallel edges count as cycles.
+    pub fn find_cycle(&self) -> Option<Vec<usize>> {
+        connectivity::find_cycle(self)
+    }
+
+    pub fn is_forest(&self) -> bool {
+        self.find_cycle().is_none()
+    }
+
+    /// A tree is a connected forest with at least one node.
+    /// Removed nodes are ignored.
+    pub fn is_tree(&self) -> bool {
+        match self.live_nodes().next() {
+            Some(u) => self.is_forest() && Bfs::new(self, u).count() == self.live_node_count(),
+            None => false
+        }
+    }
+
+    /// Closed walk using every edge exactly once, as the list of its nodes
+    /// with the first repeated at the end, or why there is none.
+    /// Empty when there are no edges.
+    pub fn eulerian_circuit(&self) -> Result<Vec<usize>, NotEulerian> {
+        eulerian::eulerian_circuit(self)
+    }
+
+    /// Walk using every edge exactly once, from a node of odd degree if
+    /// there is one, or why there is none. Empty when there are no edges.
+    pub fn eulerian_path(&self) -> Result<Vec<usize>, NotEulerian> {
+        eulerian::eulerian_path(self)
+    }
+
+    /// Hangs the tree from `root`, giving parents, depths, subtree sizes,
+    /// diameter and lowest common ancestors, or returns why the graph is
+    /// not a tree. Removed nodes are left out, and cannot be the root.
+    pub fn root_tree(&self, root: usize) -> Result<RootedTree, NotATree> {
+        RootedTree::spanning(self, root, |u| !self.removed[u])
+    }
+
+    /// Splits the nodes into two sides with every edge going from one side
+    /// to the other, or returns an odd cycle.
+    pub fn bipartition(&self) -> Result<(Vec<usize>, Vec<usize>), OddCycle> {
+        let (compact, live) = self.compact();
+        let original = |nodes: Vec<usize>| nodes.into_iter().map(|i| live[i]).collect();
+        match matching::bipartition(&compact) {
+            Ok((first, second)) => Ok((original(first), original(second))),
+            Err(cycle) => Err(OddCycle { nodes: original(cycle.nodes) })
+        }
+    }
+
+    /// Maximum matching (Hopcroft-Karp) of a bipartite graph, as `(u, v)`
+    /// pairs with `u` on the first side of `bipartition`.
+    pub fn maximum_matching(&self) -> Result<Vec<(usize, usize)>, OddCycle> {
+        let (compact, live) = self.compact();
+        match matching::maximum_matching(&compact) {
+            Ok(pairs) => Ok(pairs.into_iter().map(|(i, j)| (live[i], live[j])).collect()),
+            Err(cycle) => Err(OddCycle { nodes: cycle.nodes.into_iter().map(|i| live[i]).collect() })
+        }
+    }
+
+    /// Color of every node, numbered from 0, such that adjacent nodes have
+    /// different colors. Self-loops are ignored. Removed nodes get usize::MAX.
+    pub fn greedy_coloring(&self, order: ColoringOrder) -> Vec<usize> {
+        self.per_live_node(|g| coloring::greedy_coloring(g, order), usize::MAX)
+    }
+
+    /// Coloring with at most `k` colors, or None if there is none.
+    /// Exponential in the worst case: meant for small graphs.
+    /// Removed nodes get usize::MAX.
+    pub fn k_coloring(&self, k: usize) -> Option<Vec<usize>> {
+        let (compact, live) = self.compact();
+        let colors = coloring::k_coloring(&compact, k)?;
+        Some(self.spread(colors, &live, usize::MAX))
+    }
+
+    /// Independent set that no other node can be added to, in increasing
+    /// order.
+    pub fn maximal_independent_set(&self) -> Vec<usize> {
+        let (compact, live) = self.compact();
+        coloring::maximal_independent_set(&compact).into_iter().map(|i| live[i]).collect()
+    }
+
+    /// Largest number of edges from each node to another, or i32::MAX if
+    /// some node cannot be reached. Removed nodes get i32::MAX too.
+    pub fn eccentricity(&self) -> Vec<i32> {
+        self.per_live_node(metrics::eccentricity, i32::MAX)
+    }
+
+    /// Smallest eccentricity, or None without live nodes.
+    pub fn radius(&self) -> Option<i32> {
+        let eccentricity = self.eccentricity();
+        self.live_nodes().map(|u| eccentricity[u]).min()
+    }
+
+    /// Largest eccentricity, i32::MAX if the graph is disconnected, or None
+    /// without live nodes.
+    pub fn diameter(&self) -> Option<i32> {
+        let eccentricity = self.eccentricity();
+        self.live_nodes().map(|u| eccentricity[u]).max()
+    }
+
+    /// Closeness centrality of every node, scaled by the share of nodes it
+    /// can reach. Removed nodes get 0.
+    pub fn closeness(&self) -> Vec<f64> {
+        self.per_live_node(metrics::closeness, 0.0)
+    }
+
+    /// Betweenness centrality (Brandes) of every node, each unordered pair
+    /// of other nodes counted once. Removed nodes get 0.
+    pub fn betweenness(&self) -> Vec<f64> {
+        self.per_live_node(metrics::betweenness, 0.0)
+            .into_iter()
+            .map(|c| c / 2.0)
+            .collect()
+    }
+
+    /// Number of live nodes of each degree.
+    pub fn degree_distribution(&self) -> Vec<usize> {
+        let mut count = metrics::degree_distribution(self);
+        if let Some(isolated) = count.first_mut() {
+            *isolated -= self.free.len();
+        }
+        while count.last() == Some(&0) {
+            count.pop();
+        }
+        count
+    }
+
+    /// Local clustering coefficient of every node. Removed nodes get 0.
+    pub fn clustering(&self) -> Vec<f64> {
+        metrics::clustering(self)
+    }
+
+    /// Mean local clustering coefficient over the live nodes.
+    pub fn average_clustering(&self) -> f64 {
+        match self.live_node_count() {
+            0 => 0.0,
+            n => self.clustering().iter().sum::<f64>() / n as f64
+        }
+    }
+
+    // Copy of the graph without removed nodes or any data, with the live
+    // nodes renumbered in increasing order, and the index of each in `self`.
+    // Generic algorithms run on it so that removed nodes are not mistaken
+    // for isolated ones.
+    fn compact(&self) -> (UndirectedGraph<()>, Vec<usize>) {
+        let live: Vec<usize> = self.live_nodes().collect();
+        let mut index = vec![usize::MAX; self.size()];
+        for (i, &u) in live.iter().enumerate() {
+            index[u] = i;
+        }
+        let mut compact = UndirectedGraph::new(live.len());
+        for &(u, v, _) in self.edge_list.iter() {
+            compact.add_edge(index[u], index[v]);
+        }
+        (compact, live)
+    }
+
+    // Moves values computed on `compact` back to node indices, giving
+    // `removed` to removed nodes.
+    fn spread<R: Clone>(&self, values: Vec<R>, live: &[usize], removed: R) -> Vec<R> {
+        let mut result = vec![removed; self.size()];
+        for (value, &u) in values.into_iter().zip(live.iter()) {
+            result[u] = value;
+        }
+        result
+    }
+
+    // Runs `per_node` on the live nodes, giving `removed` to removed nodes.
+    fn per_live_node<R: Clone, F: Fn(&UndirectedGraph<()>) -> Vec<R>>(&self, per_node: F, removed: R) -> Vec<R> {
+        let (compact, live) = self.compact();
+        self.spread(per_node(&compact), &live, removed)
+    }
+
+    /// Breadth-first traversal yielding `(node, depth, parent)`, so that
+    /// nodes come layer by layer. Nodes without data are visited too.
+    pub fn bfs_ids(&self, start: usize) -> BfsTree<'_, UndirectedGraph<T, E>> {
+        assert!(self.contains_node(start));
+        BfsTree::new(self, start)
+    }
+
+    /// Depth-first traversal yielding `(node, depth, parent)` in preorder:
+    /// a node comes right after its parent in the DFS tree.
+    pub fn dfs_ids(&self, start: usize) -> DfsTree<'_, UndirectedGraph<T, E>> {
+        assert!(self.contains_node(start));
+        DfsTree::new(self, start)
+    }
+
+    /// Depth-first search from `start`, calling `visitor` on every
+    /// discovery, finish and classified edge. Each edge is reported once,
+    /// as a tree edge or a back edge; self-loops are back edges.
+    pub fn dfs_visit<F: FnMut(DfsEvent)>(&self, start: usize, visitor: F) {
+        assert!(self.contains_node(start));
+        graph::undirected_dfs_visit(self, start, visitor)
+    }
+
+    /// Freezes the graph into an immutable compressed sparse row graph,
+    /// with unit weights. Node data is not kept, and removed nodes become
+    /// isolated nodes.
+    pub fn freeze(&self) -> CsrGraph {
+        CsrGraph::build(self)
+    }
+}
+
+
+impl<T, E: Default> UndirectedGraph<T, E> {
+    /// Adds an edge carrying the default edge data.
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        self.add_edge_with(u, v, E::default());
+    }
+
+    /// Adds an edge, or returns an error if either node does not exist.
+    pub fn try_add_edge(&mut self, u: usize, v: usize) -> Result<(), GraphError> {
+        self.check_node(u)?;
+        self.check_node(v)?;
+        self.add_edge(u, v);
+        Ok(())
+    }
+
+    /// Like `try_add_edge`, but also refuses self-loops and parallel edges.
+    pub fn try_add_simple_edge(&mut self, u: usize, v: usize) -> Result<(), GraphError> {
+        self.check_node(u)?;
+        self.check_node(v)?;
+        if u == v {
+            return Err(GraphError::SelfLoopForbidden(u));
+        }
+        if self.edges[u].contains(&v) {
+            return Err(GraphError::DuplicateEdge(u, v));
+        }
+        self.add_edge(u, v);
+        Ok(())
+    }
+}
+
+
+impl<T: Clone, E> UndirectedGraph<T, E> {
+    pub fn dfs(&mut self, start: usize) -> DfsIterator<'_, T, E> {
+        assert!(self.contains_node(start));
         DfsIterator::new(self, start)
     }
 
-    pub fn bfs(&mut self, start: usize) -> BfsIterator<T> {
+    pub fn bfs(&mut self, start: usize) -> BfsIterator<'_, T, E> {
+        assert!(self.contains_node(start));
         BfsIterator::new(self, start)
     }
 }
 
+impl<T: Display, E> UndirectedGraph<T, E> {
+    /// Graphviz DOT representation. Nodes are labelled with their data,
+    /// or their index when they have none.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_highlighted(&[])
+    }
+
+    /// Same as `to_dot`, drawing the given edges and their endpoints in red,
+    /// e.g. a path from `dot::path_edges` or the edges of a tree.
+    pub fn to_dot_highlighted(&self, highlight: &[(usize, usize)]) -> String {
+        let labels: Vec<Option<String>> = (0..self.size())
+            .map(|u| self.get_data(u).map(|t| t.to_string()))
+            .collect();
+        dot::render(&EdgeList::from_undirected(self), &labels, &self.removed, false, highlight)
+    }
+}
+
+impl<T, E> GraphBase for UndirectedGraph<T, E> {
+    type Adjacent<'a> = Cloned<Iter<'a, usize>> where T: 'a, E: 'a;
+
+    fn node_count(&self) -> usize {
+        self.size()
+    }
+
+    fn adjacent(&self, u: usize) -> Self::Adjacent<'_> {
+        self.edges[u].iter().cloned()
+    }
+
+    fn degree(&self, u: usize) -> usize {
+        self.edges[u].len()
+    }
+
+    fn contains_node(&self, u: usize) -> bool {
+        UndirectedGraph::contains_node(self, u)
+    }
+}
+
+/// Every edge has weight 1.
+impl<T, E> WeightedEdges for UndirectedGraph<T, E> {
+    type WeightedAdjacent<'a> = Map<Iter<'a, usize>, fn(&usize) -> (usize, i32)> where T: 'a, E: 'a;
+
+    fn weighted_adjacent(&self, u: usize) -> Self::WeightedAdjacent<'_> {
+        self.edges[u].iter().map(|&v| (v, 1))
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serialize {
+    use std::mem;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::de::Error;
+    use serde::de::value::UnitDeserializer;
+
+    use graph_io::MAX_NODES;
+    use super::UndirectedGraph;
+
+    // Removed nodes are kept as vacant indices, but their generations are not.
+    // Edge data is left out when it carries no information, as with `()`.
+    #[derive(Serialize)]
+    struct UndirectedRef<'a, T: 'a, E: 'a> {
+        edges: Vec<(usize, usize)>,
+        #[serde(skip_serializing_if = "is_zero_sized")]
+        edge_data: Vec<&'a E>,
+        data: &'a [Option<T>],
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        removed: Vec<usize>
+    }
+
+    fn is_zero_sized<E>(_: &Vec<&E>) -> bool {
+        mem::size_of::<E>() == 0
+    }
+
+    #[derive(Deserialize)]
+    struct UndirectedRepr<T, E> {
+        edges: Vec<(usize, usize)>,
+        #[serde(default = "Option::default")]
+        edge_data: Option<Vec<E>>,
+        data: Vec<Option<T>>,
+        #[serde(default)]
+        removed: Vec<usize>
+    }
+
+    impl<T: Serialize, E: Serialize> Serialize for UndirectedGraph<T, E> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            UndirectedRef {
+                edges: self.edge_list.iter().map(|&(u, v, _)| (u, v)).collect(),
+                edge_data: self.edge_list.iter().map(|(_, _, e)| e).collect(),
+                data: &self.data,
+                removed: (0..self.size()).filter(|&u| !self.contains_node(u)).collect()
+            }.serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>, E: Deserialize<'de>> Deserialize<'de> for UndirectedGraph<T, E> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let repr = UndirectedRepr::<T, E>::deserialize(deserializer)?;
+            let n = repr.data.len();
+            if n > MAX_NODES {
+                return Err(D::Error::custom(format!("{} nodes, more than the limit of {}", n, MAX_NODES)));
+            }
+            let edge_data = match repr.edge_data {
+                Some(edge_data) => edge_data,
+                // Only edge data without content, like `()`, may be left out.
+                None => repr.edges.iter()
+                    .map(|_| E::deserialize(UnitDeserializer::<D::Error>::new()))
+                    .collect::<Result<Vec<E>, D::Error>>()
+                    .map_err(|_| D::Error::missing_field("edge_data"))?
+            };
+            if edge_data.len() != repr.edges.len() {
+                return Err(D::Error::custom(format!("{} edges but data for {}", repr.edges.len(), edge_data.len())));
+            }
+            let mut g = UndirectedGraph::new(n);
+            g.data = repr.data;
+            for ((u, v), e) in repr.edges.into_iter().zip(edge_data) {
+                if u >= n || v >= n {
+                    return Err(D::Error::custom(format!("edge {}-{} out of range for {} nodes", u, v, n)));
+                }
+                g.add_edge_with(u, v, e);
+            }
+            for u in repr.removed {
+                if !g.contains_node(u) {
+                    return Err(D::Error::custom(format!("cannot remove node {} of {}", u, n)));
+                }
+                if g.degree(u) > 0 || g.data[u].is_some() {
+                    return Err(D::Error::custom(format!("removed node {} has edges or data", u)));
+                }
+                g.remove_node(u);
+            }
+            Ok(g)
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::UndirectedGraph;
+    use coloring::{self, ColoringOrder};
+    use eulerian::NotEulerian;
+    use graph::{DfsEvent, GraphError};
+    use tree::NotATree;
 
     #[test]
     fn simple_graph () {
@@ -227,5 +851,541 @@ mod test {
         assert_eq!(s.bfs(2).collect::<Vec<i32>>(), vec![2, 0, 1, 3, 4]);
         assert_eq!(s.bfs(1).collect::<Vec<i32>>(), vec![1, 0, 3, 2, 4]);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip () {
+        use serde_json;
+
+        let mut s = UndirectedGraph::<String>::new(3);
+        s.set_data(1, "one".to_string());
+        s.add_edge(0, 1);
+        s.add_edge(1, 2);
+        s.add_edge(2, 2);
+
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(json, r#"{"edges":[[0,1],[1,2],[2,2]],"data":[null,"one",null]}"#);
+        let t: UndirectedGraph<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(t.from(1), &[0, 2]);
+        assert_eq!(t.from(2), &[1, 2, 2]);
+        assert_eq!(t.get_data(1).unwrap(), "one");
+
+        let err = serde_json::from_str::<UndirectedGraph<i32>>(r#"{"edges":[[0,1]],"data":[null]}"#).err().unwrap();
+        assert!(err.to_string().contains("edge 0-1 out of range for 1 nodes"));
+
+        s.remove_node(1);
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(json, r#"{"edges":[[2,2]],"data":[null,null,null],"removed":[1]}"#);
+        let t: UndirectedGraph<String> = serde_json::from_str(&json).unwrap();
+        assert!(!t.contains_node(1));
+        assert_eq!(t.live_node_count(), 2);
+
+        let err = serde_json::from_str::<UndirectedGraph<i32>>(r#"{"edges":[[0,1]],"data":[null,null],"removed":[1]}"#).err().unwrap();
+        assert!(err.to_string().contains("removed node 1 has edges or data"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_edge_data () {
+        use serde_json;
+
+        let mut s = UndirectedGraph::<(), String>::new(3);
+        s.add_edge_with(0, 1, "friend".to_string());
+        s.add_edge_with(2, 1, "sibling".to_string());
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(json, r#"{"edges":[[0,1],[2,1]],"edge_data":["friend","sibling"],"data":[null,null,null]}"#);
+        let t: UndirectedGraph<(), String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(t.edge_data(1, 2).unwrap(), "sibling");
+
+        let err = serde_json::from_str::<UndirectedGraph<(), String>>(r#"{"edges":[[0,1]],"data":[null,null]}"#).err().unwrap();
+        assert!(err.to_string().contains("missing field `edge_data`"));
+        let err = serde_json::from_str::<UndirectedGraph<(), String>>(r#"{"edges":[[0,1]],"edge_data":[],"data":[null,null]}"#).err().unwrap();
+        assert!(err.to_string().contains("1 edges but data for 0"));
+    }
+
+    #[test]
+    fn structure () {
+        let mut s = UndirectedGraph::<i32>::new(6);
+        s.add_edge(0, 1);
+        s.add_edge(1, 2);
+        s.add_edge(2, 0);
+        s.add_edge(2, 3);
+        s.add_edge(4, 5);
+
+        assert_eq!(s.connected_components(), vec![0, 0, 0, 0, 1, 1]);
+        assert_eq!(s.bridges(), vec![(2, 3), (4, 5)]);
+        assert_eq!(s.articulation_points(), vec![2]);
+        assert_eq!(s.biconnected_components().len(), 3);
+    }
+
+    #[test]
+    fn dynamic_nodes () {
+        let mut s = UndirectedGraph::<&str>::new(0);
+        let alice = s.add_node("alice");
+        let bob = s.add_node("bob");
+        let carol = s.add_node("carol");
+        s.add_edge(alice, bob);
+        s.add_edge(bob, carol);
+        s.add_edge(carol, alice);
+        s.add_edge(bob, bob);
+        assert_eq!(s.degree(bob), 4);
+        assert!(s.has_edge(carol, bob));
+
+        let handle = s.handle(bob);
+        assert_eq!(s.resolve(handle), Some(bob));
+        assert_eq!(s.remove_node(bob), Some("bob"));
+        assert_eq!(s.resolve(handle), None);
+        assert!(!s.contains_node(bob));
+        assert_eq!(s.live_node_count(), 2);
+        assert_eq!(s.size(), 3);
+        assert_eq!(s.live_nodes().collect::<Vec<_>>(), vec![alice, carol]);
+        assert_eq!(s.from(alice), &[carol]);
+        assert_eq!(s.from(carol), &[alice]);
+        assert_eq!(s.try_add_edge(bob, alice), Err(GraphError::RemovedNode(bob)));
+        assert_eq!(s.try_set_data(bob, "eve"), Err(GraphError::RemovedNode(bob)));
+
+        // The vacant index is reused, but the old handle stays stale.
+        let dave = s.add_node("dave");
+        assert_eq!(dave, bob);
+        assert_eq!(s.resolve(handle), None);
+        assert_eq!(s.resolve(s.handle(dave)), Some(dave));
+        assert_eq!(s.degree(dave), 0);
+        assert_eq!(s.get_data(dave), Some(&"dave"));
+
+        assert!(s.remove_edge(carol, alice));
+        assert!(!s.remove_edge(alice, carol));
+        assert!(!s.has_edge(alice, carol));
+    }
+
+    #[test]
+    fn handles () {
+        let mut s = UndirectedGraph::<&str>::new(0);
+        let alice = s.add_node("alice");
+        let alice = s.handle(alice);
+        let bob = s.add_node("bob");
+        let bob = s.handle(bob);
+        assert_eq!(s.add_edge_by_handles(alice, bob, ()), Ok(()));
+        assert_eq!(s.get_data_by_handle(bob), Some(&"bob"));
+        assert_eq!(s.remove_node_by_handle(bob), Ok(Some("bob")));
+        assert_eq!(s.remove_node_by_handle(bob), Err(GraphError::RemovedNode(1)));
+        assert_eq!(s.add_edge_by_handles(alice, bob, ()), Err(GraphError::RemovedNode(1)));
+        s.add_node("carol");
+        assert_eq!(s.get_data_by_handle(bob), None);
+        assert_eq!(s.edge_count(), 0);
+
+        s.generations[1] = u32::MAX;
+        s.remove_node(1);
+        assert_eq!(s.generations[1], 0);
+    }
+
+    // A path 0 - 1 - 2 - 3 - 4 with 1 and 3 removed, leaving three
+    // separate live nodes.
+    fn with_removed() -> UndirectedGraph<String> {
+        let mut s = UndirectedGraph::new(5);
+        for u in 0..4 {
+            s.add_edge(u, u + 1);
+        }
+        s.set_data(0, "zero".to_string());
+        s.remove_node(1);
+        s.remove_node(3);
+        s
+    }
+
+    #[test]
+    fn removed_nodes_are_ignored () {
+        let s = with_removed();
+        let none = usize::MAX;
+        assert_eq!(s.connected_components(), vec![0, none, 1, none, 2]);
+        assert_eq!(s.greedy_coloring(ColoringOrder::Natural), vec![0, none, 0, none, 0]);
+        assert_eq!(s.k_coloring(1), Some(vec![0, none, 0, none, 0]));
+        assert_eq!(s.maximal_independent_set(), vec![0, 2, 4]);
+        assert_eq!(s.bipartition(), Ok((vec![0, 2, 4], vec![])));
+        assert_eq!(s.maximum_matching(), Ok(vec![]));
+        assert!(!s.has_path(0, 1));
+        assert!(s.has_path(2, 2));
+        assert_eq!(s.shortest_unweighted_path(1, 1), None);
+        assert_eq!(s.to_dot(), concat!(
+            "graph {\n",
+            "    0 [label=\"zero\"];\n",
+            "    2 [label=\"2\"];\n",
+            "    4 [label=\"4\"];\n",
+            "}\n"
+        ));
+
+        let mut t = UndirectedGraph::<()>::new(4);
+        t.add_edge(0, 1);
+        t.add_edge(1, 2);
+        t.add_edge(2, 3);
+        t.add_edge(3, 0);
+        t.add_edge(0, 2);
+        t.remove_node(1);
+        let mut cycle = t.maximum_matching().unwrap_err().nodes;
+        cycle.sort();
+        assert_eq!(cycle, vec![0, 2, 3]);
+        t.remove_node(2);
+        assert_eq!(t.maximum_matching(), Ok(vec![(0, 3)]));
+        assert_eq!(t.bipartition(), Ok((vec![0], vec![3])));
+    }
+
+    #[test]
+    #[should_panic]
+    fn traversal_from_removed_node () {
+        with_removed().bfs_ids(1);
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Relation { Friend, Colleague }
+
+    #[test]
+    fn edge_data () {
+        let mut s = UndirectedGraph::<&str, Relation>::new(0);
+        let alice = s.add_node("alice");
+        let bob = s.add_node("bob");
+        let carol = s.add_node("carol");
+        s.add_edge_with(alice, bob, Relation::Friend);
+        s.add_edge_with(bob, carol, Relation::Colleague);
+        s.add_edge_with(carol, carol, Relation::Friend);
+        s.add_edge_with(carol, alice, Relation::Colleague);
+
+        assert_eq!(s.edge_count(), 4);
+        assert_eq!(s.edge_data(bob, alice), Some(&Relation::Friend));
+        assert_eq!(s.edge_data(carol, bob), Some(&Relation::Colleague));
+        assert_eq!(s.edge_data(alice, alice), None);
+        *s.edge_data_mut(alice, carol).unwrap() = Relation::Friend;
+        assert_eq!(s.edges().collect::<Vec<_>>(), vec![
+            (alice, bob, &Relation::Friend),
+            (bob, carol, &Relation::Colleague),
+            (carol, carol, &Relation::Friend),
+            (carol, alice, &Relation::Friend)
+        ]);
+
+        // Removing edges moves others in storage; their data must follow.
+        assert!(s.remove_edge(bob, alice));
+        assert_eq!(s.edge_data(alice, carol), Some(&Relation::Friend));
+        assert_eq!(s.edge_data(bob, carol), Some(&Relation::Colleague));
+        assert_eq!(s.remove_node(carol), Some("carol"));
+        assert_eq!(s.edge_count(), 0);
+        assert!(s.from(alice).is_empty());
+        assert!(s.from(bob).is_empty());
+
+        s.add_edge_with(alice, bob, Relation::Colleague);
+        assert_eq!(s.edges().collect::<Vec<_>>(), vec![(alice, bob, &Relation::Colleague)]);
+    }
+
+    // Every edge is listed at both ends under its own id, at the positions
+    // it records, and nothing else is.
+    fn assert_consistent<T, E>(s: &UndirectedGraph<T, E>) {
+        let mut listed = 0;
+        for u in 0..s.size() {
+            assert_eq!(s.edges[u].len(), s.edge_ids[u].len());
+            for (&v, &id) in s.edges[u].iter().zip(s.edge_ids[u].iter()) {
+                let (x, y, _) = s.edge_list[id];
+                assert!((x, y) == (u, v) || (x, y) == (v, u));
+                listed += 1;
+            }
+        }
+        assert_eq!(listed, 2 * s.edge_count());
+        assert_eq!(s.edge_positions.len(), s.edge_count());
+        for (id, &(x, y, _)) in s.edge_list.iter().enumerate() {
+            let [p, q] = s.edge_positions[id];
+            assert_eq!((s.edge_ids[x][p], s.edge_ids[y][q]), (id, id));
+            assert!(x != y || p != q);
+        }
+    }
+
+    #[test]
+    fn remove_hub () {
+        // Quadratic removal would take minutes on a hub of this degree.
+        let n = 100_000;
+        let mut s = UndirectedGraph::<(), u32>::new(n);
+        for u in 1..n {
+            s.add_edge_with(0, u, u as u32);
+            s.add_edge_with(u, u - 1, 0);
+        }
+        s.add_edge_with(0, 0, 0);
+        s.add_edge_with(0, 1, 1);
+        s.remove_node(0);
+        assert_consistent(&s);
+        assert_eq!(s.edge_count(), n - 2);
+        assert_eq!(s.degree(1), 1);
+
+        s.add_node(());
+        for _ in 0..1000 {
+            s.add_edge_with(0, 7, 7);
+        }
+        s.add_edge_with(7, 7, 0);
+        assert!(s.remove_edge(7, 0));
+        assert_consistent(&s);
+        assert_eq!(s.edge_data(7, 7), Some(&0));
+        assert_eq!(s.degree(7), 4);
+    }
+
+    #[test]
+    fn remove_hub_next_to_another () {
+        // Removing hub 0 moves the edges of hub 1, added last, into its
+        // slots: finding each of them in hub 1's list would be quadratic.
+        let k = 100_000;
+        let mut s = UndirectedGraph::<(), u32>::new(2 * k + 2);
+        for u in 0..k {
+            s.add_edge_with(0, 2 + u, 0);
+        }
+        for u in 0..k {
+            s.add_edge_with(1, 2 + k + u, u as u32);
+        }
+        s.add_edge_with(1, 1, 7);
+        s.add_edge_with(0, 1, 0);
+        s.remove_node(0);
+        assert_consistent(&s);
+        assert_eq!(s.edge_count(), k + 1);
+        assert_eq!(s.edge_data(1, 2 + k + 5), Some(&5));
+        assert_eq!(s.edge_data(1, 1), Some(&7));
+        assert!(s.remove_edge(1, 1));
+        s.remove_node(1);
+        assert_consistent(&s);
+        assert_eq!(s.edge_count(), 0);
+    }
+
+    #[test]
+    fn bipartite_matching () {
+        let mut s = UndirectedGraph::<i32>::new(5);
+        s.add_edge(0, 1);
+        s.add_edge(2, 1);
+        s.add_edge(2, 3);
+        s.remove_node(4);
+        assert_eq!(s.bipartition(), Ok((vec![0, 2], vec![1, 3])));
+        assert_eq!(s.maximum_matching(), Ok(vec![(0, 1), (2, 3)]));
+        s.add_edge(0, 2);
+        assert_eq!(s.bipartition().unwrap_err().nodes.len(), 3);
+        assert!(s.maximum_matching().is_err());
+    }
+
+    #[test]
+    fn coloring () {
+        // Registers live at the same time interfere.
+        let mut s = UndirectedGraph::<&str>::new(0);
+        let a = s.add_node("a");
+        let b = s.add_node("b");
+        let c = s.add_node("c");
+        let d = s.add_node("d");
+        s.add_edge(a, b);
+        s.add_edge(b, c);
+        s.add_edge(c, a);
+        s.add_edge(c, d);
+
+        let colors = s.greedy_coloring(ColoringOrder::DSatur);
+        assert!(coloring::is_proper_coloring(&s, &colors));
+        assert_eq!(s.k_coloring(2), None);
+        assert!(coloring::is_proper_coloring(&s, &s.k_coloring(3).unwrap()));
+
+        assert_eq!(s.maximal_independent_set(), vec![a, d]);
+        s.remove_node(a);
+        let set = s.maximal_independent_set();
+        assert_eq!(set, vec![b, d]);
+        assert!(coloring::is_independent_set(&s, &set));
+        assert!(coloring::is_maximal_independent_set(&s, &set));
+        assert!(!coloring::is_independent_set(&s, &[a, d]));
+    }
+
+    #[test]
+    fn checked_mutations () {
+        let mut s = UndirectedGraph::<i32>::new(3);
+        assert_eq!(s.try_add_edge(0, 5), Err(GraphError::NodeOutOfRange { node: 5, size: 3 }));
+        assert_eq!(s.try_add_edge(5, 0), Err(GraphError::NodeOutOfRange { node: 5, size: 3 }));
+        assert_eq!(s.try_add_edge(0, 1), Ok(()));
+        assert_eq!(s.try_add_simple_edge(1, 0), Err(GraphError::DuplicateEdge(1, 0)));
+        assert_eq!(s.try_add_simple_edge(2, 2), Err(GraphError::SelfLoopForbidden(2)));
+        assert_eq!(s.try_add_simple_edge(1, 2), Ok(()));
+        assert_eq!(s.from(1), &[0, 2]);
+
+        assert_eq!(s.try_set_data(3, 7), Err(GraphError::NodeOutOfRange { node: 3, size: 3 }));
+        assert_eq!(s.try_set_data(2, 7), Ok(()));
+        assert_eq!(s.get_data(2), Some(&7));
+    }
+
+    struct NotClone;
+
+    #[test]
+    fn traversal_ids () {
+        let mut s = UndirectedGraph::<NotClone>::new(6);
+        s.add_edge(0, 1);
+        s.add_edge(0, 2);
+        s.add_edge(1, 3);
+        s.add_edge(2, 3);
+        s.add_edge(3, 4);
+        s.set_data(4, NotClone);
+
+        assert_eq!(s.bfs_ids(0).collect::<Vec<_>>(), vec![
+            (0, 0, None), (1, 1, Some(0)), (2, 1, Some(0)), (3, 2, Some(1)), (4, 3, Some(3))
+        ]);
+        assert_eq!(s.dfs_ids(0).collect::<Vec<_>>(), vec![
+            (0, 0, None), (1, 1, Some(0)), (3, 2, Some(1)), (2, 3, Some(3)), (4, 3, Some(3))
+        ]);
+        assert_eq!(s.bfs_ids(5).collect::<Vec<_>>(), vec![(5, 0, None)]);
+
+        // Five edges: four tree edges and the back edge closing 0-1-3-2.
+        let mut edges = Vec::new();
+        s.dfs_visit(0, |e| match e {
+            DfsEvent::TreeEdge(..) | DfsEvent::BackEdge(..) => edges.push(e),
+            _ => {}
+        });
+        assert_eq!(edges, vec![
+            DfsEvent::TreeEdge(0, 1), DfsEvent::TreeEdge(1, 3), DfsEvent::TreeEdge(3, 2),
+            DfsEvent::BackEdge(2, 0), DfsEvent::TreeEdge(3, 4)
+        ]);
+    }
+
+    #[test]
+    fn paths_and_cycles () {
+        let mut s = UndirectedGraph::<i32>::new(6);
+        s.add_edge(0, 1);
+        s.add_edge(1, 2);
+        s.add_edge(0, 3);
+        s.add_edge(3, 2);
+        s.add_edge(4, 5);
+
+        assert!(s.has_path(0, 2));
+        assert!(!s.has_path(0, 5));
+        assert_eq!(s.shortest_unweighted_path(2, 0), Some(vec![2, 1, 0]));
+        assert_eq!(s.shortest_unweighted_path(5, 5), Some(vec![5]));
+        assert_eq!(s.shortest_unweighted_path(0, 4), None);
+        assert_eq!(s.find_cycle(), Some(vec![0, 1, 2, 3]));
+        assert!(!s.is_forest());
+        assert!(!s.is_tree());
+    }
+
+    #[test]
+    fn trees_and_forests () {
+        let mut s = UndirectedGraph::<i32>::new(4);
+        s.add_edge(0, 1);
+        s.add_edge(1, 2);
+        assert!(s.is_forest());
+        assert!(!s.is_tree());
+        s.add_edge(3, 1);
+        assert!(s.is_tree());
+        s.add_edge(3, 1);
+        assert!(!s.is_forest());
+
+        assert!(UndirectedGraph::<i32>::new(1).is_tree());
+        assert!(!UndirectedGraph::<i32>::new(0).is_tree());
+        assert!(UndirectedGraph::<i32>::new(0).is_forest());
+
+        s.remove_node(0);
+        assert!(!s.is_forest());
+        s.remove_edge(1, 3);
+        s.add_edge(1, 3);
+        assert!(s.is_tree());
+    }
+
+    #[test]
+    fn rooted_tree () {
+        let mut s = UndirectedGraph::<i32>::new(5);
+        s.add_edge(0, 1);
+        s.add_edge(1, 2);
+        s.add_edge(1, 3);
+        s.add_edge(3, 4);
+        let tree = s.root_tree(1).unwrap();
+        assert_eq!(tree.depths(), &[Some(1), Some(0), Some(1), Some(1), Some(2)]);
+        assert_eq!(tree.subtree_sizes(), vec![1, 5, 1, 2, 1]);
+        assert_eq!(tree.diameter(), 3);
+        assert_eq!(tree.binary_lifting().lca(0, 4), 1);
+        assert_eq!(tree.euler_tour().lca(4, 3), 3);
+
+        s.add_edge(4, 2);
+        assert_eq!(s.root_tree(0).unwrap_err(), NotATree::Cycle(vec![3, 1, 2, 4]));
+        s.remove_node(4);
+        s.remove_node(0);
+        assert_eq!(s.root_tree(2).unwrap().parents(), &[None, Some(2), None, Some(1), None]);
+        s.remove_edge(1, 3);
+        assert_eq!(s.root_tree(2).unwrap_err(), NotATree::Unreachable(3));
+        assert_eq!(s.root_tree(0).unwrap_err(), NotATree::InvalidRoot(0));
+        assert_eq!(s.root_tree(5).unwrap_err(), NotATree::InvalidRoot(5));
+    }
+
+    #[test]
+    fn eulerian () {
+        // A bow tie: two triangles sharing node 2
+        let mut s = UndirectedGraph::<i32>::new(5);
+        for &(u, v) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2)].iter() {
+            s.add_edge(u, v);
+        }
+        let circuit = s.eulerian_circuit().unwrap();
+        assert_eq!(circuit.len(), 7);
+        assert_eq!(circuit.first(), circuit.last());
+
+        s.remove_edge(3, 4);
+        assert_eq!(s.eulerian_circuit(), Err(NotEulerian::OddDegree(vec![3, 4])));
+        let path = s.eulerian_path().unwrap();
+        assert_eq!((path[0], path[5]), (3, 4));
+
+        s.remove_node(4);
+        s.add_node(0);
+        s.add_edge(3, 4);
+        s.remove_edge(2, 3);
+        assert_eq!(s.eulerian_path(), Err(NotEulerian::Disconnected(3, 0)));
+    }
+
+    #[test]
+    fn metrics () {
+        let mut s = UndirectedGraph::<i32>::new(5);
+        s.add_edge(0, 1);
+        s.add_edge(1, 2);
+        s.add_edge(2, 3);
+        s.add_edge(3, 4);
+        assert_eq!(s.diameter(), Some(4));
+        assert_eq!(s.betweenness(), vec![0.0, 3.0, 4.0, 3.0, 0.0]);
+
+        // Removing an end leaves a shorter path, not a disconnected graph.
+        s.remove_node(4);
+        assert_eq!(s.eccentricity(), vec![3, 2, 2, 3, i32::MAX]);
+        assert_eq!(s.radius(), Some(2));
+        assert_eq!(s.diameter(), Some(3));
+        assert_eq!(s.closeness()[4], 0.0);
+        assert_eq!(s.betweenness(), vec![0.0, 2.0, 2.0, 0.0, 0.0]);
+        assert_eq!(s.degree_distribution(), vec![0, 2, 2]);
+        s.add_edge(0, 2);
+        assert_eq!(s.clustering(), vec![1.0, 1.0, 1.0 / 3.0, 0.0, 0.0]);
+        assert!((s.average_clustering() - (7.0 / 3.0) / 4.0).abs() < 1e-9);
+
+        s.remove_node(0);
+        s.remove_node(1);
+        s.remove_node(2);
+        s.remove_node(3);
+        assert_eq!(s.diameter(), None);
+        assert_eq!(s.degree_distribution(), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn to_dot () {
+        let mut s = UndirectedGraph::<String>::new(3);
+        s.set_data(0, "paris".to_string());
+        s.set_data(2, "\"big\" apple".to_string());
+        s.add_edge(0, 1);
+        s.add_edge(1, 2);
+
+        assert_eq!(s.to_dot(), concat!(
+            "graph {\n",
+            "    0 [label=\"paris\"];\n",
+            "    1 [label=\"1\"];\n",
+            "    2 [label=\"\\\"big\\\" apple\"];\n",
+            "    0 -- 1;\n",
+            "    1 -- 2;\n",
+            "}\n"));
+    }
+
+    #[test]
+    fn to_dot_highlighted_tree () {
+        let mut s = UndirectedGraph::<i32>::new(4);
+        s.add_edge(0, 1);
+        s.add_edge(0, 2);
+        s.add_edge(1, 2);
+        s.add_edge(2, 3);
+
+        let dot = s.to_dot_highlighted(&[(1, 0), (0, 2)]);
+        assert!(dot.contains("    0 -- 1 [color=red, penwidth=2];\n"));
+        assert!(dot.contains("    0 -- 2 [color=red, penwidth=2];\n"));
+        assert!(dot.contains("    1 -- 2;\n"));
+        assert!(dot.contains("    3 [label=\"3\"];\n"));
+        assert!(dot.contains("    2 [label=\"2\", color=red];\n"));
+    }
 }
 
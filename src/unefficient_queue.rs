@@ -76,6 +76,30 @@ impl<'a, T: 'a> Iterator for Iter<'a, T> {
     }
 }
 
+#[cfg(feature = "serde")]
+mod serialize {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Queue;
+
+    // Serialized as a sequence of elements from front to back.
+    impl<T: Serialize> Serialize for Queue<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter())
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for Queue<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let mut queue = Queue::new();
+            for t in Vec::<T>::deserialize(deserializer)? {
+                queue.enqueue(t);
+            }
+            Ok(queue)
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Queue;
@@ -139,4 +163,19 @@ mod test {
             i = i + 1;
         }
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        use serde_json;
+
+        let mut q = Queue::new();
+        q.enqueue(1);
+        q.enqueue(2);
+        q.enqueue(3);
+        let json = serde_json::to_string(&q).unwrap();
+        assert_eq!(json, "[1,2,3]");
+        let q: Queue<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(q.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
 }
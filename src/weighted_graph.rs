@@ -1,12 +1,22 @@
+use std::iter::Map;
+use std::slice::Iter;
+
+use csr_graph::CsrGraph;
+use dot;
+use graph::{GraphBase, GraphError, WeightedEdges};
+use hamiltonian::{self, TooManyNodes, Tour};
+use graph_io::EdgeList;
+use matching::{self, Assignment, InvalidAssignment, OddCycle};
+use metrics;
+use shortest_paths;
+
+pub use shortest_paths::FloydShortestPaths;
+
 pub struct WeightedGraph {
     n_nodes: usize,
     out: Vec<Vec<(usize, i32)>>
 }
 
-pub struct FloydShortestPaths {
-    w: Vec<Vec<i32>>
-}
-
 impl WeightedGraph {
     pub fn new(size: usize) -> WeightedGraph {
         let mut g = WeightedGraph {
@@ -19,6 +29,53 @@ impl WeightedGraph {
         g
     }
 
+    pub fn size(&self) -> usize {
+        self.n_nodes
+    }
+
+    /// Adds an isolated node and returns its index.
+    pub fn add_node(&mut self) -> usize {
+        self.out.push(Vec::new());
+        self.n_nodes += 1;
+        self.n_nodes - 1
+    }
+
+    /// Removes `u` and all its edges.
+    /// Indices are kept contiguous: the last node is moved to index `u`.
+    pub fn remove_node(&mut self, u: usize) {
+        assert!(u < self.n_nodes);
+        let edges = self.out.swap_remove(u);
+        self.n_nodes -= 1;
+        let moved = self.n_nodes;
+        if u == moved {
+            for &(v, _) in edges.iter().filter(|&&(v, _)| v != u) {
+                self.out[v].retain(|&(x, _)| x != u);
+            }
+            return;
+        }
+        for &(v, _) in edges.iter().filter(|&&(v, _)| v != u) {
+            // The last node now lives at index `u`.
+            let v = if v == moved { u } else { v };
+            self.out[v].retain(|&(x, _)| x != u);
+        }
+        // Rename `moved` to `u`, including in the moved node's own self-loops.
+        for &mut (ref mut v, _) in self.out[u].iter_mut() {
+            if *v == moved {
+                *v = u;
+            }
+        }
+        let neighbors: Vec<usize> = self.out[u].iter().map(|&(v, _)| v).collect();
+        for v in neighbors {
+            for &mut (ref mut x, _) in self.out[v].iter_mut() {
+                if *x == moved {
+                    *x = u;
+                }
+            }
+        }
+    }
+
+    /// Adds an undirected edge between `u` and `v`.
+    /// Parallel edges are allowed: adding u-v twice keeps both edges.
     pub fn add_edge(&mut self, u: usize, v: usize, weight: i32) {
         assert!(u < self.n_nodes);
         assert!(v < self.n_nodes);
@@ -26,47 +83,280 @@ impl WeightedGraph {
         self.out[v].push((u, weight));
     }
 
-    pub fn get_floyd(&self) -> FloydShortestPaths {
-        FloydShortestPaths::compute(self)
+    /// Adds an undirected edge, or returns an error if either node does not exist.
+    pub fn try_add_edge(&mut self, u: usize, v: usize, weight: i32) -> Result<(), GraphError> {
+        GraphError::check_node(u, self.n_nodes)?;
+        GraphError::check_node(v, self.n_nodes)?;
+        self.add_edge(u, v, weight);
+        Ok(())
     }
-}
 
-impl FloydShortestPaths {
-    fn compute(graph: &WeightedGraph) -> FloydShortestPaths {
-        let mut sp = FloydShortestPaths {
-            // Initialize a N x N x N matrix with maxint
-            w: vec![vec![i32::max_value(); graph.n_nodes]; graph.n_nodes]
-        };
-        for u in 0..graph.n_nodes {
-            for &(v, weight) in graph.out[u].iter() {
-                sp.w[u][v] = weight;
-            }
+    /// Like `try_add_edge`, but also refuses self-loops and parallel edges.
+    pub fn try_add_simple_edge(&mut self, u: usize, v: usize, weight: i32) -> Result<(), GraphError> {
+        GraphError::check_node(u, self.n_nodes)?;
+        GraphError::check_node(v, self.n_nodes)?;
+        if u == v {
+            return Err(GraphError::SelfLoopForbidden(u));
         }
-        for k in 0..graph.n_nodes {
-            for u in 0..graph.n_nodes {
-                for v in 0..graph.n_nodes {
-                    if sp.w[u][k] == i32::max_value() || sp.w[k][v] == i32::max_value() {
-                        continue;
-                    }
-                    if sp.w[u][v] > sp.w[u][k] + sp.w[k][v] {
-                        sp.w[u][v] = sp.w[u][k] + sp.w[k][v];
-                    }
+        if self.out[u].iter().any(|&(x, _)| x == v) {
+            return Err(GraphError::DuplicateEdge(u, v));
+        }
+        self.add_edge(u, v, weight);
+        Ok(())
+    }
 
-                }
+    /// Removes every edge between `u` and `v`.
+    /// Returns false if there was no such edge.
+    pub fn remove_edge(&mut self, u: usize, v: usize) -> bool {
+        assert!(u < self.n_nodes);
+        assert!(v < self.n_nodes);
+        let before = self.out[u].len();
+        self.out[u].retain(|&(x, _)| x != v);
+        self.out[v].retain(|&(x, _)| x != u);
+        self.out[u].len() != before
+    }
+
+    /// Sets the weight of every edge between `u` and `v`.
+    /// Returns false if there was no such edge.
+    pub fn set_weight(&mut self, u: usize, v: usize, weight: i32) -> bool {
+        assert!(u < self.n_nodes);
+        assert!(v < self.n_nodes);
+        let mut found = false;
+        for &mut (x, ref mut w) in self.out[u].iter_mut() {
+            if x == v {
+                *w = weight;
+                found = true;
             }
         }
-        sp
+        for &mut (x, ref mut w) in self.out[v].iter_mut() {
+            if x == u {
+                *w = weight;
+            }
+        }
+        found
+    }
+
+    /// Weight of the edge between `u` and `v`.
+    /// With parallel edges, this is the smallest of their weights.
+    pub fn edge_weight(&self, u: usize, v: usize) -> Option<i32> {
+        assert!(u < self.n_nodes);
+        assert!(v < self.n_nodes);
+        self.out[u].iter()
+            .filter(|&&(x, _)| x == v)
+            .map(|&(_, weight)| weight)
+            .min()
+    }
+
+    /// Graphviz DOT representation, with weights as edge labels.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_highlighted(&[])
+    }
+
+    /// Same as `to_dot`, drawing the given edges and their endpoints in red,
+    /// e.g. a path from `dot::path_edges`.
+    pub fn to_dot_highlighted(&self, highlight: &[(usize, usize)]) -> String {
+        dot::render(&EdgeList::from_weighted(self), &[], &[], true, highlight)
+    }
+
+    /// Iterates over the `(neighbor, weight)` pairs of `u`, one per edge.
+    /// Parallel edges are yielded once each, self-loops twice.
+    pub fn neighbors(&self, u: usize) -> impl Iterator<Item = (usize, i32)> + '_ {
+        assert!(u < self.n_nodes);
+        self.out[u].iter().cloned()
+    }
+
+    pub fn get_floyd(&self) -> FloydShortestPaths {
+        shortest_paths::floyd(self)
+    }
+
+    /// Computes the same matrix as `get_floyd` with a cache-friendly blocked
+    /// Floyd-Warshall spread over `n_threads` threads.
+    pub fn get_floyd_parallel(&self, n_threads: usize) -> FloydShortestPaths {
+        shortest_paths::floyd_parallel(self, n_threads)
+    }
+
+    /// Computes all-pairs shortest paths with Johnson's algorithm.
+    /// Returns None if the graph contains a negative cycle.
+    pub fn get_johnson(&self) -> Option<FloydShortestPaths> {
+        shortest_paths::johnson(self)
+    }
+
+    /// Computes all-pairs shortest paths, using Johnson's algorithm on
+    /// sparse graphs and Floyd-Warshall on dense ones.
+    /// Returns None if the graph contains a negative cycle.
+    pub fn get_all_pairs_shortest_paths(&self) -> Option<FloydShortestPaths> {
+        shortest_paths::all_pairs_shortest_paths(self)
+    }
+
+    /// Single source shortest path lengths from `source` (Dijkstra).
+    /// All weights must be non-negative. Unreachable nodes get i32::MAX.
+    pub fn get_dijkstra(&self, source: usize) -> Vec<i32> {
+        shortest_paths::dijkstra(self, source)
     }
 
-    pub fn get_shortest_path(&self, u: usize, v: usize) -> i32 {
-        self.w[u][v]
+    /// Splits the nodes into two sides with every edge going from one side
+    /// to the other, or returns an odd cycle.
+    pub fn bipartition(&self) -> Result<(Vec<usize>, Vec<usize>), OddCycle> {
+        matching::bipartition(self)
+    }
+
+    /// Minimum cost assignment of `workers` to distinct `jobs`, or of jobs
+    /// to workers if there are fewer jobs (Hungarian algorithm). None if the
+    /// smaller side cannot be entirely assigned.
+    pub fn min_cost_assignment(&self, workers: &[usize], jobs: &[usize]) -> Result<Option<Assignment>, InvalidAssignment> {
+        matching::min_cost_assignment(self, workers, jobs)
+    }
+
+    /// Largest distance from each node to another, or i32::MAX if some node
+    /// cannot be reached. All weights must be non-negative.
+    pub fn eccentricity(&self) -> Vec<i32> {
+        metrics::eccentricity(self)
+    }
+
+    /// Smallest eccentricity, or None for a graph without nodes.
+    pub fn radius(&self) -> Option<i32> {
+        metrics::radius(self)
+    }
+
+    /// Largest eccentricity, i32::MAX if the graph is disconnected, or None
+    /// for a graph without nodes.
+    pub fn diameter(&self) -> Option<i32> {
+        metrics::diameter(self)
+    }
+
+    /// Closeness centrality of every node, scaled by the share of nodes it
+    /// can reach. All weights must be non-negative.
+    pub fn closeness(&self) -> Vec<f64> {
+        metrics::closeness(self)
+    }
+
+    /// Betweenness centrality (Brandes) of every node, each unordered pair
+    /// of other nodes counted once. All weights must be positive.
+    pub fn betweenness(&self) -> Vec<f64> {
+        metrics::betweenness(self).into_iter().map(|c| c / 2.0).collect()
+    }
+
+    /// Number of nodes of each degree.
+    pub fn degree_distribution(&self) -> Vec<usize> {
+        metrics::degree_distribution(self)
+    }
+
+    /// Local clustering coefficient of every node, ignoring weights.
+    pub fn clustering(&self) -> Vec<f64> {
+        metrics::clustering(self)
+    }
+
+    /// Mean local clustering coefficient.
+    pub fn average_clustering(&self) -> f64 {
+        metrics::average_clustering(self)
+    }
+
+    /// Cheapest path through every node exactly once (Held-Karp), or None
+    /// if there is none. Fails beyond HELD_KARP_MAX_NODES nodes.
+    pub fn shortest_hamiltonian_path(&self) -> Result<Option<Tour>, TooManyNodes> {
+        hamiltonian::shortest_hamiltonian_path(self)
+    }
+
+    /// Cheapest cycle through every node exactly once from node 0, the
+    /// travelling salesman tour (Held-Karp), or None if there is none.
+    /// Fails beyond HELD_KARP_MAX_NODES nodes.
+    pub fn shortest_hamiltonian_cycle(&self) -> Result<Option<Tour>, TooManyNodes> {
+        hamiltonian::shortest_hamiltonian_cycle(self)
+    }
+
+    /// Freezes the graph into an immutable compressed sparse row graph.
+    pub fn freeze(&self) -> CsrGraph {
+        CsrGraph::build(self)
     }
 }
 
+impl GraphBase for WeightedGraph {
+    type Adjacent<'a> = Map<Iter<'a, (usize, i32)>, fn(&(usize, i32)) -> usize>;
+
+    fn node_count(&self) -> usize {
+        self.n_nodes
+    }
+
+    fn adjacent(&self, u: usize) -> Self::Adjacent<'_> {
+        self.out[u].iter().map(|&(v, _)| v)
+    }
+
+    fn degree(&self, u: usize) -> usize {
+        self.out[u].len()
+    }
+}
+
+impl WeightedEdges for WeightedGraph {
+    type WeightedAdjacent<'a> = ::std::iter::Cloned<Iter<'a, (usize, i32)>>;
+
+    fn weighted_adjacent(&self, u: usize) -> Self::WeightedAdjacent<'_> {
+        self.out[u].iter().cloned()
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serialize {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::de::Error;
+
+    use graph_io::{EdgeList, MAX_NODES};
+    use super::WeightedGraph;
+
+    impl Serialize for WeightedGraph {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            EdgeList::from_weighted(self).serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for WeightedGraph {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let list = EdgeList::deserialize(deserializer)?;
+            let n = list.n_nodes;
+            if n > MAX_NODES {
+                return Err(D::Error::custom(format!("{} nodes, more than the limit of {}", n, MAX_NODES)));
+            }
+            if let Some(&(u, v, _)) = list.edges.iter().find(|&&(u, v, _)| u >= n || v >= n) {
+                return Err(D::Error::custom(format!("edge {}-{} out of range for {} nodes", u, v, n)));
+            }
+            Ok(list.to_weighted())
+        }
+    }
+}
 
 #[cfg(test)]
 mod test {
-    use super::WeightedGraph;
+    use super::{FloydShortestPaths, WeightedGraph};
+    use dot;
+    use graph::GraphError;
+    use test_util::{sample_graph, Lcg};
+
+    #[test]
+    fn assignment() {
+        // Two drivers (0, 1) and three deliveries (2, 3, 4), weighted by distance.
+        let mut g = WeightedGraph::new(5);
+        g.add_edge(0, 2, 5);
+        g.add_edge(0, 3, 2);
+        g.add_edge(1, 3, 3);
+        g.add_edge(1, 4, 9);
+        assert_eq!(g.bipartition(), Ok((vec![0, 1], vec![2, 3, 4])));
+        let assignment = g.min_cost_assignment(&[0, 1], &[2, 3, 4]).unwrap().unwrap();
+        assert_eq!(assignment.cost, 8);
+        assert_eq!(assignment.pairs, vec![(0, 2), (1, 3)]);
+    }
+
+    #[test]
+    fn checked_edges() {
+        let mut g = WeightedGraph::new(3);
+        assert_eq!(g.try_add_edge(0, 3, 1), Err(GraphError::NodeOutOfRange { node: 3, size: 3 }));
+        assert_eq!(g.try_add_edge(1, 1, 1), Ok(()));
+        assert_eq!(g.try_add_simple_edge(0, 1, 2), Ok(()));
+        assert_eq!(g.try_add_simple_edge(1, 0, 5), Err(GraphError::DuplicateEdge(1, 0)));
+        assert_eq!(g.try_add_simple_edge(2, 2, 5), Err(GraphError::SelfLoopForbidden(2)));
+        assert_eq!(g.try_add_simple_edge(7, 2, 5).unwrap_err().to_string(),
+                   "node 7 out of range for a graph of 3 nodes");
+        assert_eq!(g.edge_weight(0, 1), Some(2));
+        assert_eq!(g.neighbors(2).count(), 0);
+    }
 
     #[test]
     fn basics() {
@@ -122,4 +412,370 @@ mod test {
         assert_eq!(floyd.get_shortest_path(0, 6), 2);
         assert_eq!(floyd.get_shortest_path(3, 1), 7);
     }
+
+    #[test]
+    fn add_and_remove_edges() {
+        let mut g = WeightedGraph::new(3);
+        g.add_edge(0, 1, 4);
+        g.add_edge(1, 2, 2);
+        assert_eq!(g.edge_weight(0, 1), Some(4));
+        assert_eq!(g.edge_weight(1, 0), Some(4));
+        assert_eq!(g.edge_weight(0, 2), None);
+
+        assert!(g.remove_edge(1, 0));
+        assert!(!g.remove_edge(0, 1));
+        assert_eq!(g.edge_weight(0, 1), None);
+        assert_eq!(g.neighbors(1).collect::<Vec<_>>(), vec![(2, 2)]);
+        assert_eq!(g.get_floyd().get_shortest_path(0, 2), i32::MAX);
+    }
+
+    #[test]
+    fn parallel_edges() {
+        let mut g = WeightedGraph::new(2);
+        g.add_edge(0, 1, 5);
+        g.add_edge(0, 1, 3);
+        assert_eq!(g.edge_weight(0, 1), Some(3));
+        assert_eq!(g.neighbors(0).collect::<Vec<_>>(), vec![(1, 5), (1, 3)]);
+
+        assert!(g.set_weight(1, 0, 7));
+        assert_eq!(g.neighbors(0).collect::<Vec<_>>(), vec![(1, 7), (1, 7)]);
+        assert_eq!(g.neighbors(1).collect::<Vec<_>>(), vec![(0, 7), (0, 7)]);
+
+        assert!(g.remove_edge(0, 1));
+        assert_eq!(g.neighbors(0).count(), 0);
+        assert_eq!(g.neighbors(1).count(), 0);
+    }
+
+    #[test]
+    fn set_weight() {
+        let mut g = sample_graph();
+        assert!(g.set_weight(0, 5, 10));
+        assert!(!g.set_weight(0, 9, 1));
+        assert_eq!(g.edge_weight(5, 0), Some(10));
+        assert_eq!(g.get_floyd().get_shortest_path(0, 5), 7);
+    }
+
+    #[test]
+    fn add_node() {
+        let mut g = WeightedGraph::new(2);
+        g.add_edge(0, 1, 1);
+        assert_eq!(g.add_node(), 2);
+        assert_eq!(g.size(), 3);
+        g.add_edge(1, 2, 1);
+        assert_eq!(g.get_floyd().get_shortest_path(0, 2), 2);
+    }
+
+    #[test]
+    fn remove_node_remaps_last() {
+        let mut g = WeightedGraph::new(4);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 2);
+        g.add_edge(2, 3, 3);
+        g.add_edge(3, 0, 4);
+        g.add_edge(3, 3, 5);
+
+        g.remove_node(1);
+        // Node 3 is now node 1.
+        assert_eq!(g.size(), 3);
+        assert_eq!(g.edge_weight(0, 1), Some(4));
+        assert_eq!(g.edge_weight(2, 1), Some(3));
+        assert_eq!(g.edge_weight(1, 1), Some(5));
+        assert_eq!(g.edge_weight(0, 2), None);
+        assert_eq!(g.get_floyd().get_shortest_path(0, 2), 7);
+    }
+
+    #[test]
+    fn remove_node_adjacent_to_last() {
+        let mut g = WeightedGraph::new(3);
+        g.add_edge(0, 2, 1);
+        g.add_edge(0, 1, 2);
+        g.add_edge(1, 2, 3);
+
+        g.remove_node(0);
+        assert_eq!(g.size(), 2);
+        assert_eq!(g.neighbors(0).collect::<Vec<_>>(), vec![(1, 3)]);
+        assert_eq!(g.neighbors(1).collect::<Vec<_>>(), vec![(0, 3)]);
+    }
+
+    #[test]
+    fn remove_last_node() {
+        let mut g = WeightedGraph::new(3);
+        g.add_edge(0, 2, 1);
+        g.add_edge(1, 2, 1);
+        g.remove_node(2);
+        assert_eq!(g.size(), 2);
+        assert_eq!(g.neighbors(0).count(), 0);
+        assert_eq!(g.neighbors(1).count(), 0);
+    }
+
+    // Both matrices agree on every pair of the first `n` nodes.
+    fn assert_same_paths(a: &FloydShortestPaths, b: &FloydShortestPaths, n: usize) {
+        for u in 0..n {
+            for v in 0..n {
+                assert_eq!(a.get_shortest_path(u, v), b.get_shortest_path(u, v), "path {} -> {}", u, v);
+            }
+        }
+    }
+
+    #[test]
+    fn floyd_parallel_edges() {
+        let mut g = WeightedGraph::new(2);
+        g.add_edge(0, 1, 3);
+        g.add_edge(0, 1, 5);
+        assert_eq!(g.get_floyd().get_shortest_path(0, 1), 3);
+    }
+
+    #[test]
+    fn update_edge() {
+        let mut g = sample_graph();
+        let mut floyd = g.get_floyd();
+
+        g.add_edge(0, 9, 3);
+        floyd.update_edge(0, 9, 3);
+        assert_eq!(floyd.get_shortest_path(0, 9), 3);
+        assert_eq!(floyd.get_shortest_path(5, 9), 4);
+        assert_same_paths(&floyd, &g.get_floyd(), 10);
+    }
+
+    #[test]
+    fn update_edge_connects_components() {
+        let mut g = WeightedGraph::new(4);
+        g.add_edge(0, 1, 1);
+        g.add_edge(2, 3, 1);
+        let mut floyd = g.get_floyd();
+
+        g.add_edge(1, 2, 5);
+        floyd.update_edge(1, 2, 5);
+        assert_eq!(floyd.get_shortest_path(0, 3), 7);
+        assert_same_paths(&floyd, &g.get_floyd(), 4);
+    }
+
+    #[test]
+    fn update_self_loop() {
+        let mut g = WeightedGraph::new(2);
+        g.add_edge(0, 1, 4);
+        let mut floyd = g.get_floyd();
+
+        g.add_edge(1, 1, 1);
+        floyd.update_edge(1, 1, 1);
+        assert_same_paths(&floyd, &g.get_floyd(), 2);
+    }
+
+    #[test]
+    fn update_edge_matches_recompute_on_random_graphs() {
+        let mut rng = Lcg(42);
+        for _ in 0..20 {
+            let n = 2 + rng.next(15);
+            let mut g = WeightedGraph::new(n);
+            for _ in 0..rng.next(2 * n) {
+                g.add_edge(rng.next(n), rng.next(n), 1 + rng.next(20) as i32);
+            }
+            let mut floyd = g.get_floyd();
+            for _ in 0..5 {
+                let (u, v) = (rng.next(n), rng.next(n));
+                let weight = 1 + rng.next(20) as i32;
+                if rng.next(2) == 0 {
+                    g.add_edge(u, v, weight);
+                } else if let Some(old) = g.edge_weight(u, v) {
+                    // Decrease an existing edge.
+                    let weight = weight.min(old);
+                    g.set_weight(u, v, weight);
+                } else {
+                    continue;
+                }
+                floyd.update_edge(u, v, g.edge_weight(u, v).unwrap());
+                assert_same_paths(&floyd, &g.get_floyd(), n);
+            }
+        }
+    }
+
+    #[test]
+    fn parallel_floyd() {
+        let g = sample_graph();
+        assert_same_paths(&g.get_floyd_parallel(4), &g.get_floyd(), 10);
+    }
+
+    #[test]
+    fn parallel_floyd_matches_serial_on_random_graphs() {
+        let mut rng = Lcg(7);
+        for &n in [0, 1, 63, 64, 65, 130].iter() {
+            let mut g = WeightedGraph::new(n);
+            for _ in 0..2 * n {
+                g.add_edge(rng.next(n), rng.next(n), 1 + rng.next(50) as i32);
+            }
+            let floyd = g.get_floyd();
+            for &n_threads in [1, 3, 8].iter() {
+                assert_same_paths(&g.get_floyd_parallel(n_threads), &floyd, n);
+            }
+        }
+    }
+
+    #[test]
+    fn to_dot() {
+        let mut g = WeightedGraph::new(3);
+        g.add_edge(0, 1, 4);
+        g.add_edge(2, 1, -1);
+        assert_eq!(g.to_dot(), concat!(
+            "graph {\n",
+            "    0 [label=\"0\"];\n",
+            "    1 [label=\"1\"];\n",
+            "    2 [label=\"2\"];\n",
+            "    0 -- 1 [label=\"4\"];\n",
+            "    1 -- 2 [label=\"-1\"];\n",
+            "}\n"));
+    }
+
+    #[test]
+    fn to_dot_highlighted_path() {
+        let g = sample_graph();
+        let dot = g.to_dot_highlighted(&dot::path_edges(&[0, 5, 6]));
+        assert!(dot.contains("    0 [label=\"0\", color=red];\n"));
+        assert!(dot.contains("    6 [label=\"6\", color=red];\n"));
+        assert!(dot.contains("    1 [label=\"1\"];\n"));
+        assert!(dot.contains("    0 -- 5 [label=\"1\", color=red, penwidth=2];\n"));
+        assert!(dot.contains("    5 -- 6 [label=\"1\", color=red, penwidth=2];\n"));
+        assert!(dot.contains("    0 -- 1 [label=\"4\"];\n"));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        use serde_json;
+
+        let g = sample_graph();
+        let json = serde_json::to_string(&g).unwrap();
+        let h: WeightedGraph = serde_json::from_str(&json).unwrap();
+        assert_same_paths(&h.get_floyd(), &g.get_floyd(), 10);
+
+        let json = serde_json::to_string(&g.get_floyd()).unwrap();
+        let floyd: FloydShortestPaths = serde_json::from_str(&json).unwrap();
+        assert_same_paths(&floyd, &g.get_floyd(), 10);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_invalid() {
+        use serde_json;
+
+        let err = serde_json::from_str::<WeightedGraph>(r#"{"n_nodes": 2, "edges": [[0, 2, 1]]}"#).err().unwrap();
+        assert!(err.to_string().contains("edge 0-2 out of range for 2 nodes"));
+        let err = serde_json::from_str::<WeightedGraph>(r#"{"n_nodes": 1000000000000000, "edges": []}"#).err().unwrap();
+        assert!(err.to_string().contains("1000000000000000 nodes, more than the limit"));
+        let err = serde_json::from_str::<FloydShortestPaths>(r#"{"n": 2, "w": [0, 1, 2]}"#).err().unwrap();
+        assert!(err.to_string().contains("expected 2 x 2 matrix, found 3 values"));
+    }
+
+    #[test]
+    fn johnson_matches_floyd() {
+        let g = sample_graph();
+        let floyd = g.get_floyd();
+        let johnson = g.get_johnson().unwrap();
+        for u in 0..10 {
+            for v in 0..10 {
+                assert_eq!(johnson.get_shortest_path(u, v), floyd.get_shortest_path(u, v));
+            }
+        }
+    }
+
+    #[test]
+    fn johnson_disconnected() {
+        let mut g = WeightedGraph::new(4);
+        g.add_edge(0, 1, 3);
+        g.add_edge(2, 3, 1);
+
+        let johnson = g.get_johnson().unwrap();
+        assert_eq!(johnson.get_shortest_path(0, 1), 3);
+        assert_eq!(johnson.get_shortest_path(0, 2), i32::MAX);
+        assert_eq!(johnson.get_shortest_path(0, 0), 6);
+        assert_eq!(johnson.get_shortest_path(3, 3), 2);
+    }
+
+    #[test]
+    fn johnson_negative_cycle() {
+        let mut g = WeightedGraph::new(3);
+        g.add_edge(0, 1, 2);
+        g.add_edge(1, 2, -1);
+
+        assert!(g.get_johnson().is_none());
+    }
+
+    #[test]
+    fn all_pairs_picks_an_algorithm() {
+        let g = sample_graph();
+        let floyd = g.get_floyd();
+        let sp = g.get_all_pairs_shortest_paths().unwrap();
+        for u in 0..10 {
+            for v in 0..10 {
+                assert_eq!(sp.get_shortest_path(u, v), floyd.get_shortest_path(u, v));
+            }
+        }
+
+        // Any negative edge of an undirected graph is a negative cycle,
+        // whichever algorithm is picked.
+        let mut sparse = WeightedGraph::new(100);
+        sparse.add_edge(3, 4, -1);
+        assert!(sparse.get_all_pairs_shortest_paths().is_none());
+        let mut dense = WeightedGraph::new(3);
+        dense.add_edge(0, 1, 1);
+        dense.add_edge(1, 2, -1);
+        dense.add_edge(2, 0, 1);
+        assert!(dense.get_all_pairs_shortest_paths().is_none());
+
+        // Floyd alone would overflow going around the cycles.
+        let mut complete = WeightedGraph::new(40);
+        for u in 0..40 {
+            for v in 0..u {
+                complete.add_edge(u, v, -1);
+            }
+        }
+        assert!(complete.get_all_pairs_shortest_paths().is_none());
+    }
+
+    #[test]
+    fn hamiltonian() {
+        let g = sample_graph();
+        let cost = |nodes: &[usize]| -> i64 {
+            nodes.windows(2).map(|e| g.edge_weight(e[0], e[1]).unwrap() as i64).sum()
+        };
+        let path = g.shortest_hamiltonian_path().unwrap().unwrap();
+        assert_eq!(path.cost, 18);
+        assert_eq!(cost(&path.nodes), 18);
+        let mut nodes = path.nodes.clone();
+        nodes.sort();
+        assert_eq!(nodes, (0..10).collect::<Vec<_>>());
+
+        let mut cycle = g.shortest_hamiltonian_cycle().unwrap().unwrap();
+        assert_eq!(cycle.cost, 34);
+        cycle.nodes.push(0);
+        assert_eq!(cost(&cycle.nodes), 34);
+    }
+
+    #[test]
+    fn metrics() {
+        // A square 0-1-2-3 with a heavy diagonal 0-2
+        let mut g = WeightedGraph::new(4);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(2, 3, 1);
+        g.add_edge(3, 0, 1);
+        g.add_edge(0, 2, 3);
+        assert_eq!(g.eccentricity(), vec![2, 2, 2, 2]);
+        assert_eq!(g.radius(), Some(2));
+        assert_eq!(g.diameter(), Some(2));
+        // 0 and 2 are joined through 1 or through 3.
+        assert_eq!(g.betweenness(), vec![0.5, 0.5, 0.5, 0.5]);
+        assert_eq!(g.degree_distribution(), vec![0, 0, 2, 2]);
+        assert_eq!(g.clustering(), vec![2.0 / 3.0, 1.0, 2.0 / 3.0, 1.0]);
+    }
+
+    #[test]
+    fn dijkstra() {
+        let g = sample_graph();
+        let floyd = g.get_floyd();
+        let dist = g.get_dijkstra(3);
+        assert_eq!(dist[3], 0);
+        for v in (0..10).filter(|&v| v != 3) {
+            assert_eq!(dist[v], floyd.get_shortest_path(3, v));
+        }
+    }
 }